@@ -0,0 +1,336 @@
+use std::{
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bitboard::{BitBoard, MAX_EXP},
+    board::{Board, FOUR_CHANCE},
+    direction::Direction,
+    tile::Tile,
+};
+
+/// Default number of moves the [`Solver`] looks ahead
+pub const DEFAULT_DEPTH: usize = 3;
+/// Maximum number of moves the [`Solver`] looks ahead, deeper search takes
+/// too long even on the smallest board
+pub const MAX_DEPTH: usize = 8;
+
+/// Branches less probable than this aren't searched any deeper
+const MIN_PROB: f64 = 0.0001;
+
+const LOST_PENALTY: f64 = 200000.;
+const EMPTY_WEIGHT: f64 = 270.;
+const MERGES_WEIGHT: f64 = 700.;
+const MONOTONICITY_POWER: f64 = 4.;
+const MONOTONICITY_WEIGHT: f64 = 47.;
+const SUM_POWER: f64 = 3.5;
+const SUM_WEIGHT: f64 = 11.;
+
+/// AI picking moves using expectimax search over the tile spawns
+pub struct Solver {
+    depth: usize,
+    /// Chance of spawning 4 on the searched board
+    four_chance: f64,
+}
+
+/// Move recommended by the [`Solver`]
+#[derive(Debug, Clone, Copy)]
+pub struct Hint {
+    pub dir: Direction,
+    /// Score gained by the move
+    pub gain: usize,
+}
+
+impl Solver {
+    /// Creates new [`Solver`] looking `depth` moves ahead, the depth is
+    /// limited to [`MAX_DEPTH`]
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.clamp(1, MAX_DEPTH),
+            four_chance: FOUR_CHANCE,
+        }
+    }
+
+    /// Gets the best move on the given [`Board`], returns [`None`] when no
+    /// move is possible
+    pub fn best_move(&self, board: &Board) -> Option<Direction> {
+        let solver = self.for_board(board);
+        match solver.bitboard(board, self.depth) {
+            Some(bits) => solver.search(&bits, self.depth, None).flatten(),
+            None => solver.search(board, self.depth, None).flatten(),
+        }
+    }
+
+    /// Gets the best move on the given [`Board`] within the time budget.
+    /// It searches deeper and deeper until the budget or the [`Solver`]
+    /// depth is reached, so it stays responsive on large boards
+    pub fn timed_move(
+        &self,
+        board: &Board,
+        budget: Duration,
+    ) -> Option<Direction> {
+        let deadline = Instant::now() + budget;
+        let solver = self.for_board(board);
+        match solver.bitboard(board, self.depth) {
+            Some(bits) => solver.deepening(&bits, deadline),
+            None => solver.deepening(board, deadline),
+        }
+    }
+
+    /// Gets recommended move on the given [`Board`] within the time budget,
+    /// see [`Solver::timed_move`]
+    pub fn hint(&self, board: &Board, budget: Duration) -> Option<Hint> {
+        let dir = self.timed_move(board, budget)?;
+        let mut next = board.clone();
+        next.slide(dir);
+        Some(Hint {
+            dir,
+            gain: next.score - board.score,
+        })
+    }
+
+    /// Gets copy of the [`Solver`] using the spawn chances of the [`Board`]
+    fn for_board(&self, board: &Board) -> Self {
+        Self {
+            four_chance: board.four_chance(),
+            ..*self
+        }
+    }
+
+    /// Gets the [`Board`] as [`BitBoard`] when it's possible to search
+    /// `depth` moves ahead without merging tiles above [`MAX_EXP`]
+    fn bitboard(&self, board: &Board, depth: usize) -> Option<BitBoard> {
+        board
+            .bitboard()
+            .filter(|bits| (bits.max_exp() as usize + depth) < MAX_EXP as usize)
+    }
+
+    /// Searches deeper and deeper until the deadline or the [`Solver`] depth
+    /// is reached, returns the best move of the deepest finished search
+    fn deepening<P: Position>(
+        &self,
+        board: &P,
+        deadline: Instant,
+    ) -> Option<Direction> {
+        let mut dir = self.search(board, 1, None).flatten()?;
+        for depth in 2..=self.depth {
+            match self.search(board, depth, Some(deadline)) {
+                Some(best) => dir = best?,
+                None => break,
+            }
+        }
+        Some(dir)
+    }
+
+    /// Searches for the best move looking `depth` moves ahead. Returns
+    /// [`None`] when the deadline was exceeded
+    fn search<P: Position>(
+        &self,
+        board: &P,
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<Option<Direction>> {
+        let mut best = None;
+        for dir in Direction::ALL {
+            let mut next = board.clone();
+            if !next.slide(dir) {
+                continue;
+            }
+            let value = self.chance(&next, depth - 1, 1., deadline)?;
+            if best.is_none_or(|(_, best)| value > best) {
+                best = Some((dir, value));
+            }
+        }
+        Some(best.map(|(dir, _)| dir))
+    }
+
+    /// Gets expected value of the board before the tile is spawned
+    fn chance<P: Position>(
+        &self,
+        board: &P,
+        depth: usize,
+        prob: f64,
+        deadline: Option<Instant>,
+    ) -> Option<f64> {
+        let empty = board.empty();
+        if depth == 0 || prob < MIN_PROB || empty.is_empty() {
+            return Some(board.heuristic());
+        }
+        if deadline.is_some_and(|d| Instant::now() > d) {
+            return None;
+        }
+
+        let prob = prob / empty.len() as f64;
+        let (two, four) = (1. - self.four_chance, self.four_chance);
+        let mut next = board.clone();
+        let mut res = 0.;
+        for pos in empty.iter().copied() {
+            next.set(pos, 1);
+            res += self.max(&next, depth, prob * two, deadline)? * two;
+            next.set(pos, 2);
+            res += self.max(&next, depth, prob * four, deadline)? * four;
+            next.set(pos, 0);
+        }
+        Some(res / empty.len() as f64)
+    }
+
+    /// Gets value of the best move on the board
+    fn max<P: Position>(
+        &self,
+        board: &P,
+        depth: usize,
+        prob: f64,
+        deadline: Option<Instant>,
+    ) -> Option<f64> {
+        let mut best = 0.;
+        for dir in Direction::ALL {
+            let mut next = board.clone();
+            if next.slide(dir) {
+                best = f64::max(
+                    best,
+                    self.chance(&next, depth - 1, prob, deadline)?,
+                );
+            }
+        }
+        Some(best)
+    }
+
+    /// Estimates how good single row or column is. It prefers empty tiles,
+    /// possible merges and lines with monotonic values
+    fn line_heuristic(line: &[u8]) -> f64 {
+        let mut sum = 0.;
+        let mut empty = 0;
+        let mut merges = 0;
+        let mut prev = 0;
+        let mut counter = 0;
+        for &exp in line {
+            sum += (exp as f64).powf(SUM_POWER);
+            if exp == 0 {
+                empty += 1;
+                continue;
+            }
+            if prev == exp {
+                counter += 1;
+            } else if counter > 0 {
+                merges += 1 + counter;
+                counter = 0;
+            }
+            prev = exp;
+        }
+        if counter > 0 {
+            merges += 1 + counter;
+        }
+
+        let mut mono_left = 0.;
+        let mut mono_right = 0.;
+        for pair in line.windows(2) {
+            let a = (pair[0] as f64).powf(MONOTONICITY_POWER);
+            let b = (pair[1] as f64).powf(MONOTONICITY_POWER);
+            if a > b {
+                mono_left += a - b;
+            } else {
+                mono_right += b - a;
+            }
+        }
+
+        LOST_PENALTY + EMPTY_WEIGHT * empty as f64
+            - MONOTONICITY_WEIGHT * f64::min(mono_left, mono_right)
+            + MERGES_WEIGHT * merges as f64
+            - SUM_WEIGHT * sum
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEPTH)
+    }
+}
+
+/// Board representation, which can be searched by the [`Solver`]
+trait Position: Clone {
+    /// Moves tiles in given direction, returns whether any tile moved
+    fn slide(&mut self, dir: Direction) -> bool;
+
+    /// Gets positions of the empty tiles
+    fn empty(&self) -> Vec<usize>;
+
+    /// Sets exponent of the tile on the given position
+    fn set(&mut self, pos: usize, exp: u8);
+
+    /// Estimates how good the board is, based on its rows and columns
+    fn heuristic(&self) -> f64;
+}
+
+impl Position for Board {
+    fn slide(&mut self, dir: Direction) -> bool {
+        Board::slide(self, dir)
+    }
+
+    fn empty(&self) -> Vec<usize> {
+        self.tiles()
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, tile)| tile.is_empty().then_some(pos))
+            .collect()
+    }
+
+    fn set(&mut self, pos: usize, exp: u8) {
+        self.set_tile(pos, Tile::from_exp(exp));
+    }
+
+    fn heuristic(&self) -> f64 {
+        let (width, height) = self.size();
+        let tiles = self.tiles();
+        let mut line = Vec::with_capacity(width.max(height));
+
+        let mut res = 0.;
+        for y in 0..height {
+            line.clear();
+            line.extend(
+                tiles[y * width..(y + 1) * width].iter().map(Tile::exp),
+            );
+            res += Solver::line_heuristic(&line);
+        }
+        for x in 0..width {
+            line.clear();
+            line.extend((0..height).map(|y| tiles[y * width + x].exp()));
+            res += Solver::line_heuristic(&line);
+        }
+        res
+    }
+}
+
+impl Position for BitBoard {
+    fn slide(&mut self, dir: Direction) -> bool {
+        let before = *self;
+        BitBoard::slide(self, dir);
+        *self != before
+    }
+
+    fn empty(&self) -> Vec<usize> {
+        (0..16).filter(|pos| self.get(*pos) == 0).collect()
+    }
+
+    fn set(&mut self, pos: usize, exp: u8) {
+        BitBoard::set(self, pos, exp);
+    }
+
+    fn heuristic(&self) -> f64 {
+        static TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+        let table = TABLE.get_or_init(|| {
+            (0..=u16::MAX)
+                .map(|row| {
+                    let line =
+                        [0, 1, 2, 3].map(|i| (row >> (i * 4) & 0xf) as u8);
+                    Solver::line_heuristic(&line)
+                })
+                .collect()
+        });
+        self.rows()
+            .into_iter()
+            .chain(self.cols())
+            .map(|line| table[line as usize])
+            .sum()
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+use tui2048::{outcome::MoveOutcome, tile::Tile};
+
+/// Part of the animation in which the tiles slide, merged tiles pop and
+/// the new tile fades in during the rest of it
+const SLIDE_PART: f64 = 0.6;
+
+/// Animation of a single move, from the tiles before the move to the
+/// current [`Board`](tui2048::board::Board)
+pub struct Animation {
+    tiles: Vec<Tile>,
+    outcome: MoveOutcome,
+    start: Instant,
+    duration: Duration,
+}
+
+impl Animation {
+    /// Creates new [`Animation`] starting now. `tiles` are the tiles before
+    /// the move described by the `outcome`
+    pub fn new(
+        tiles: Vec<Tile>,
+        outcome: MoveOutcome,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            tiles,
+            outcome,
+            start: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Gets the tiles before the move
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Gets outcome of the animated move
+    pub fn outcome(&self) -> &MoveOutcome {
+        &self.outcome
+    }
+
+    /// Checks whether the [`Animation`] is finished
+    pub fn is_done(&self) -> bool {
+        self.start.elapsed() >= self.duration
+    }
+
+    /// Gets how far the tiles slid, `None` when they already finished
+    /// sliding. Slide slows down at the end
+    pub fn slide(&self) -> Option<f64> {
+        let progress = self.progress() / SLIDE_PART;
+        (progress < 1.0).then(|| 1.0 - (1.0 - progress).powi(2))
+    }
+
+    /// Gets progress of popping merged tiles and fading in the new tile
+    pub fn appear(&self) -> f64 {
+        ((self.progress() - SLIDE_PART) / (1.0 - SLIDE_PART)).clamp(0.0, 1.0)
+    }
+
+    /// Gets progress of the whole [`Animation`] from 0 to 1
+    fn progress(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = self.start.elapsed().as_secs_f64();
+        (elapsed / self.duration.as_secs_f64()).min(1.0)
+    }
+}
@@ -1,11 +1,8 @@
-use std::{
-    io::{stdout, Write},
-    time::Duration,
-};
+use std::time::{Duration, Instant};
 
 use crossterm::{
-    event::{poll, read, Event, KeyCode, KeyEvent},
-    terminal::{disable_raw_mode, enable_raw_mode},
+    event::{poll, read, Event, KeyCode, KeyEvent, KeyModifiers},
+    terminal::size,
 };
 use termint::{
     enums::fg::Fg,
@@ -13,79 +10,537 @@ use termint::{
     term::Term,
     widgets::{layout::Layout, spacer::Spacer, span::StrSpanExtension},
 };
+use tui2048::{
+    ai::{Hint, Solver},
+    board::Board,
+    direction::Direction,
+    game_status::GameStatus,
+    history::History,
+    replay::Replay,
+};
 
 use crate::{
-    board::Board, error::Error, game_status::GameStatus, raw_span::RawSpan,
+    animation::Animation,
+    color_mode::ColorMode,
+    error::Error,
+    guard::TermGuard,
+    keys::{Action, Keys},
+    player::Player,
+    raw_span::RawSpan,
+    renderer::{Renderer, TileSize},
+    replays,
+    save::Save,
+    scores::{ScoreKey, Scores},
+    theme::Theme,
 };
 
 /// Represents which screen is currently shown
 pub enum Screen {
     Game,
     Help,
+    Resume,
+    LoadError(String),
+    Scores,
+    Replay,
 }
 
 pub struct App {
     board: Board,
+    history: History,
     status: GameStatus,
     screen: Screen,
+    saved: Option<Save>,
+    scores: Scores,
+    scores_error: Option<String>,
+    /// Index of the high-score table shown on the scores screen
+    scores_table: usize,
+    /// Table and position of the last added record
+    last_record: Option<(usize, usize)>,
+    recorded: bool,
+    played: Duration,
+    since: Instant,
+    renderer: Renderer,
+    solver: Solver,
+    autoplay: bool,
+    /// Delay between the moves made by autoplay
+    ai_delay: Duration,
+    next_ai: Instant,
+    hint: Option<Hint>,
+    /// Time budget for searching the hint
+    hint_time: Duration,
+    /// Recording of the current game, [`None`] when the game was loaded
+    /// from a save without it
+    replay: Option<Replay>,
+    /// Undone moves of the recording, used when they're redone
+    undone: Vec<(Direction, Duration)>,
+    player: Option<Player>,
+    /// Keeps the saved game of the previous session on exit, used when the
+    /// app wasn't started with it
+    keep_save: bool,
+    /// Message shown in the status line until the next key press
+    message: Option<String>,
+    animation: Option<Animation>,
+    /// Duration of the move animation, zero disables the animation
+    anim_time: Duration,
+    /// Tile size set with the zoom keys, [`None`] picks the largest one
+    /// fitting the terminal
+    zoom: Option<TileSize>,
+    keys: Keys,
+    /// Asks for confirmation before quitting the game
+    confirm_quit: bool,
+    /// Asks for confirmation before restarting the game
+    confirm_restart: bool,
+    /// Action waiting for confirmation
+    pending: Option<Action>,
+    /// Themes which can be cycled through
+    themes: Vec<Theme>,
+    /// Index of the currently used theme
+    theme: usize,
     term: Term,
 }
 
+/// Default delay between the moves made by autoplay
+pub const DEFAULT_AI_DELAY: Duration = Duration::from_millis(200);
+/// Default time budget for searching the hint
+pub const DEFAULT_HINT_TIME: Duration = Duration::from_millis(200);
+/// Default duration of the move animation
+pub const DEFAULT_ANIM_TIME: Duration = Duration::from_millis(150);
+/// Delay between the animation frames
+const FRAME_TIME: Duration = Duration::from_millis(16);
+const MIN_AI_DELAY: Duration = Duration::from_millis(10);
+const MAX_AI_DELAY: Duration = Duration::from_secs(2);
+
 impl App {
     /// Creates new [`App`]
-    pub fn new(width: usize, height: usize) -> Self {
+    pub fn new(board: Board) -> Self {
+        let (scores, scores_error) = match Scores::load() {
+            Ok(scores) => (scores, None),
+            Err(e) => (Scores::default(), Some(e.to_string())),
+        };
+        let replay = Replay::new(&board);
         Self {
-            board: Board::new(width, height),
+            board,
+            history: History::default(),
             status: GameStatus::Playing,
             screen: Screen::Game,
+            saved: None,
+            scores,
+            scores_error,
+            scores_table: 0,
+            last_record: None,
+            recorded: false,
+            played: Duration::ZERO,
+            since: Instant::now(),
+            renderer: Renderer::new(ColorMode::TrueColor),
+            solver: Solver::default(),
+            autoplay: false,
+            ai_delay: DEFAULT_AI_DELAY,
+            next_ai: Instant::now(),
+            hint: None,
+            hint_time: DEFAULT_HINT_TIME,
+            replay: Some(replay),
+            undone: vec![],
+            player: None,
+            keep_save: false,
+            message: None,
+            animation: None,
+            anim_time: DEFAULT_ANIM_TIME,
+            zoom: None,
+            keys: Keys::default(),
+            confirm_quit: false,
+            confirm_restart: false,
+            pending: None,
+            themes: Theme::builtin(),
+            theme: 0,
             term: Term::new(),
         }
     }
 
+    /// Loads saved game from the previous session and offers it for resume
+    pub fn resume(mut self) -> Self {
+        match Save::load() {
+            Ok(Some(save)) => {
+                self.saved = Some(save);
+                self.screen = Screen::Resume;
+            }
+            Ok(None) => {}
+            Err(e) => self.screen = Screen::LoadError(e.to_string()),
+        }
+        self
+    }
+
+    /// Continues game from the given [`Save`]
+    pub fn with_save(mut self, save: Save) -> Self {
+        self.load_save(save);
+        self
+    }
+
+    /// Starts on the replay screen playing back the given replay
+    pub fn with_player(mut self, player: Player) -> Self {
+        self.player = Some(player);
+        self.screen = Screen::Replay;
+        self.keep_save = true;
+        self
+    }
+
+    /// Keeps the saved game of the previous session, so it's not replaced
+    /// by the current game
+    pub fn keep_save(mut self) -> Self {
+        self.keep_save = true;
+        self
+    }
+
+    /// Sets colors supported by the terminal
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.renderer.set_color_mode(mode);
+        self
+    }
+
+    /// Sets whether only ASCII characters are drawn
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.renderer.set_ascii(ascii);
+        self
+    }
+
+    /// Sets key bindings of the game screen
+    pub fn keys(mut self, keys: Keys) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Sets whether quitting and restarting the game has to be confirmed
+    pub fn confirm(mut self, quit: bool, restart: bool) -> Self {
+        self.confirm_quit = quit;
+        self.confirm_restart = restart;
+        self
+    }
+
+    /// Sets themes which can be cycled through and the index of the used one
+    pub fn themes(mut self, themes: Vec<Theme>, current: usize) -> Self {
+        if let Some(theme) = themes.get(current) {
+            self.renderer.set_theme(theme.clone());
+            self.themes = themes;
+            self.theme = current;
+        }
+        self
+    }
+
+    /// Sets message shown in the status line until the first key press
+    pub fn message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// Sets how many moves ahead the AI looks
+    pub fn ai_depth(mut self, depth: usize) -> Self {
+        self.solver = Solver::new(depth);
+        self
+    }
+
+    /// Sets delay between the moves made by autoplay
+    pub fn ai_delay(mut self, delay: Duration) -> Self {
+        self.ai_delay = delay;
+        self
+    }
+
+    /// Sets time budget for searching the hint
+    pub fn hint_time(mut self, time: Duration) -> Self {
+        self.hint_time = time;
+        self
+    }
+
+    /// Sets duration of the move animation, zero disables the animation
+    pub fn anim_time(mut self, time: Duration) -> Self {
+        self.anim_time = time;
+        self
+    }
+
+    /// Sets how many moves can be undone
+    pub fn undo_depth(mut self, depth: usize) -> Self {
+        self.history.set_depth(depth);
+        self
+    }
+
     /// Runs the [`App`]
     pub fn run(&mut self) -> Result<(), Error> {
-        // Saves screen, clears screen and hides cursor
-        print!("\x1b[?1049h\x1b[2J\x1b[?25l");
-        _ = stdout().flush();
-        enable_raw_mode()?;
+        let guard = TermGuard::new()?;
+        let res = self.main_loop(&guard);
+        drop(guard);
+
+        match res {
+            Err(Error::Exit) => self.save(),
+            res => res,
+        }
+    }
+
+    /// Saves the game so it can be resumed on the next start. When the save
+    /// is kept, only played game is saved and only if there is no save yet
+    fn save(&mut self) -> Result<(), Error> {
+        match self.screen {
+            Screen::Resume | Screen::LoadError(_) => return Ok(()),
+            _ => {}
+        }
+        if self.keep_save && (Save::exists() || self.board.moves() == 0) {
+            return Ok(());
+        }
+        if self.status == GameStatus::GameOver {
+            return Save::remove();
+        }
+        let played = self.played();
+        let board = std::mem::take(&mut self.board);
+        Save::new(board, self.status, played, self.replay.take()).store()
+    }
 
-        let res = self.main_loop();
+    /// Gets how long the current game has been played
+    fn played(&self) -> Duration {
+        self.played + self.since.elapsed()
+    }
 
-        disable_raw_mode()?;
-        // Restores screen
-        print!("\x1b[?1049l\x1b[?25h");
-        _ = stdout().flush();
+    /// Adds the current game to the high-scores, only once per game. It's
+    /// done when the game is over or when it's abandoned by restarting
+    fn record_score(&mut self) {
+        if self.recorded || self.board.moves() == 0 {
+            return;
+        }
+        self.recorded = true;
 
-        res
+        let played = self.played();
+        self.last_record = self.scores.add(&self.board, played).map(|pos| {
+            let key = ScoreKey::new(&self.board);
+            let table = self.scores.tables().iter().position(|t| t.key == key);
+            (table.unwrap_or_default(), pos)
+        });
+        self.store_scores();
+    }
+
+    /// Removes the recorded result of the current game from the
+    /// high-scores, used when undo continues the finished game
+    fn unrecord_score(&mut self) {
+        if !self.recorded {
+            return;
+        }
+        self.recorded = false;
+        if let Some((table, pos)) = self.last_record.take() {
+            self.scores.remove(table, pos);
+            self.store_scores();
+        }
+    }
+
+    /// Saves the high-scores, unless loading them failed
+    fn store_scores(&mut self) {
+        if self.scores_error.is_none() {
+            if let Err(e) = self.scores.store() {
+                self.scores_error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Replaces the current game with the given saved game
+    fn load_save(&mut self, save: Save) {
+        self.board = save.board;
+        self.status = save.status;
+        self.replay = save.replay;
+        self.undone.clear();
+        self.history.clear();
+        self.hint = None;
+        self.recorded = self.status == GameStatus::GameOver;
+        self.played = save.played;
+        self.since = Instant::now();
+    }
+
+    /// Starts new game
+    fn restart(&mut self) {
+        self.record_score();
+        self.board.reset();
+        self.replay = Some(Replay::new(&self.board));
+        self.undone.clear();
+        self.history.clear();
+        self.hint = None;
+        self.status = GameStatus::Playing;
+        self.recorded = false;
+        self.played = Duration::ZERO;
+        self.since = Instant::now();
     }
 
     /// Main loop of the [`App`]
-    fn main_loop(&mut self) -> Result<(), Error> {
+    fn main_loop(&mut self, guard: &TermGuard) -> Result<(), Error> {
         self.render();
         loop {
-            if poll(Duration::from_millis(100))? {
+            if guard.should_exit() {
+                return Err(Error::Exit);
+            }
+            let timeout = if self.animation.is_some() {
+                FRAME_TIME
+            } else if let Some(next) = self.next_tick() {
+                next.saturating_duration_since(Instant::now())
+            } else {
+                Duration::from_millis(100)
+            };
+            if poll(timeout)? {
+                // Key press skips the animation
+                if self.animation.take().is_some() {
+                    self.render();
+                }
                 self.key_listener()?;
+            } else if self.animation.is_some() {
+                self.animate();
+            } else if self.next_tick().is_some_and(|t| Instant::now() >= t) {
+                match self.screen {
+                    Screen::Replay => self.replay_step(),
+                    _ => self.ai_move(),
+                }
             }
         }
     }
 
+    /// Gets time of the next move made by autoplay or replay playback
+    fn next_tick(&self) -> Option<Instant> {
+        match (&self.screen, &self.player) {
+            (Screen::Replay, Some(player)) => player.next(),
+            _ => self.autoplay.then_some(self.next_ai),
+        }
+    }
+
+    /// Renders next frame of the animation, removes it when it's finished
+    fn animate(&mut self) {
+        if self.animation.as_ref().is_some_and(|a| a.is_done()) {
+            self.animation = None;
+        }
+        self.render();
+    }
+
+    /// Makes move chosen by the AI, stops autoplay when the game ends. The
+    /// search is limited by the delay between the moves, so the keys are
+    /// still handled on large boards
+    fn ai_move(&mut self) {
+        self.next_ai = Instant::now() + self.ai_delay;
+        if !matches!(self.screen, Screen::Game) {
+            return;
+        }
+
+        match self.solver.timed_move(&self.board, self.ai_delay) {
+            Some(dir) => self.make_move(dir),
+            None => self.autoplay = false,
+        }
+        if matches!(self.status, GameStatus::GameOver | GameStatus::Victory) {
+            self.autoplay = false;
+            print!("\x1b[H\x1b[J");
+        }
+        self.render();
+    }
+
     /// Renders the [`App`]
-    fn render(&self) {
+    fn render(&mut self) {
+        self.fit_tiles();
+        let (width, height) = self.min_size();
+        if let Ok((cols, rows)) = size() {
+            if (cols as usize) < width || (rows as usize) < height {
+                self.render_too_small(width, height);
+                return;
+            }
+        }
+
         match self.screen {
             Screen::Game => self.render_game(),
             Screen::Help => self.render_help(),
+            Screen::Resume => self.render_resume(),
+            Screen::LoadError(ref msg) => self.render_load_error(msg),
+            Screen::Scores => self.render_scores(),
+            Screen::Replay => self.render_replay(),
+        }
+    }
+
+    /// Gets the smallest terminal size needed for rendering the current
+    /// screen
+    fn min_size(&self) -> (usize, usize) {
+        let Some(board) = self.shown_board() else {
+            return (0, 0);
+        };
+        // Board with the status line above it and the info line below it
+        (
+            self.renderer.board_width(board),
+            self.renderer.board_height(board) + 2,
+        )
+    }
+
+    /// Gets the [`Board`] shown on the current screen
+    fn shown_board(&self) -> Option<&Board> {
+        match (&self.screen, &self.player) {
+            (Screen::Game, _) => Some(&self.board),
+            (Screen::Replay, Some(player)) => Some(player.board()),
+            _ => None,
         }
     }
 
+    /// Sets the tile size picked with the zoom keys, or the largest one
+    /// with which the board fits the terminal
+    fn fit_tiles(&mut self) {
+        let size = match (self.zoom, self.shown_board(), size()) {
+            (Some(zoom), _, _) => zoom,
+            (None, Some(board), Ok((cols, rows))) => {
+                self.renderer.fit_tile_size(
+                    board,
+                    cols as usize,
+                    (rows as usize).saturating_sub(2),
+                )
+            }
+            _ => return,
+        };
+        if size != self.renderer.tile_size() {
+            print!("\x1b[H\x1b[J");
+            self.renderer.set_tile_size(size);
+        }
+    }
+
+    /// Changes the tile size, [`None`] switches to the automatic size
+    fn zoom(&mut self, zoom: Option<TileSize>) {
+        self.zoom = zoom;
+        let name = zoom.map(|size| size.name()).unwrap_or("auto");
+        self.message = Some(format!("Tiles: {name}"));
+    }
+
+    /// Renders screen informing that the terminal is too small
+    fn render_too_small(&self, width: usize, height: usize) {
+        print!("\x1b[H\x1b[J");
+        let mut wrapper = Layout::vertical().center();
+        wrapper.add_child("Terminal too small", Constrain::Length(1));
+        wrapper.add_child(
+            format!("need {width}x{height}").fg(self.renderer.highlight()),
+            Constrain::Length(1),
+        );
+
+        let mut main = Layout::horizontal().center();
+        main.add_child(wrapper, Constrain::Length(18));
+
+        _ = self.term.render(main);
+    }
+
     /// Handles key listening of the [`App`]
     fn key_listener(&mut self) -> Result<(), Error> {
-        let Event::Key(KeyEvent { code, .. }) = read()? else {
-            return Ok(());
+        let code = match read()? {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers,
+                ..
+            }) if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(Error::Exit)
+            }
+            Event::Key(KeyEvent { code, .. }) => code,
+            Event::Resize(..) => {
+                print!("\x1b[H\x1b[J");
+                self.render();
+                return Ok(());
+            }
+            _ => return Ok(()),
         };
 
         match self.screen {
             Screen::Game => self.game_listener(code),
             Screen::Help => self.help_listener(code),
+            Screen::Resume => self.resume_listener(code),
+            Screen::LoadError(_) => self.load_error_listener(code),
+            Screen::Scores => self.scores_listener(code),
+            Screen::Replay => self.replay_listener(code),
         }
     }
 
@@ -93,76 +548,595 @@ impl App {
     fn render_game(&self) {
         let mut wrapper = Layout::vertical().center();
         wrapper.add_child(self.render_status(), Constrain::Length(1));
+        let board = match &self.animation {
+            Some(anim) => self.renderer.animation(&self.board, anim),
+            None => self.renderer.board(&self.board),
+        };
         wrapper.add_child(
-            self.board.get(),
-            Constrain::Length(self.board.height()),
+            board,
+            Constrain::Length(self.renderer.board_height(&self.board)),
+        );
+        if self.status == GameStatus::Victory {
+            wrapper.add_child(
+                format!("Press {} to keep going", self.key(Action::KeepGoing))
+                    .fg(self.renderer.highlight()),
+                Constrain::Length(1),
+            );
+        } else if self.autoplay {
+            wrapper.add_child(
+                format!(
+                    "Autoplay, press {} to stop",
+                    self.key(Action::Autoplay)
+                )
+                .fg(self.renderer.accent()),
+                Constrain::Length(1),
+            );
+        } else {
+            wrapper.add_child(
+                format!(
+                    "{}Press {} for help",
+                    self.symbol("🛈 ", ""),
+                    self.key(Action::Help)
+                )
+                .fg(self.renderer.hint()),
+                Constrain::Length(1),
+            );
+        }
+
+        let mut main = Layout::horizontal().center();
+        main.add_child(
+            wrapper,
+            Constrain::Length(self.renderer.board_width(&self.board)),
+        );
+
+        _ = self.term.render(main);
+    }
+
+    /// Renders the help screen
+    fn render_help(&self) {
+        let moves = self.keys_or(
+            &[Action::Left, Action::Up, Action::Down, Action::Right],
+            "←↑↓→",
+            "arrows",
         );
+        let controls = [
+            (moves, "tiles movement"),
+            (self.key(Action::Undo), "undo move"),
+            (self.key(Action::Redo), "redo move"),
+            (self.key(Action::KeepGoing), "keep going"),
+            (self.key(Action::Hint), "show hint"),
+            (self.key(Action::Autoplay), "toggle autoplay"),
+            (
+                format!(
+                    "{} {}",
+                    self.key(Action::Slower),
+                    self.key(Action::Faster)
+                ),
+                "autoplay speed",
+            ),
+            (self.key(Action::Restart), "restart game"),
+            (self.key(Action::Scores), "high scores"),
+            (self.key(Action::Replay), "replay game"),
+            (self.key(Action::SaveReplay), "save replay"),
+            (
+                format!(
+                    "{} {}",
+                    self.key(Action::ZoomIn),
+                    self.key(Action::ZoomOut)
+                ),
+                "zoom tiles",
+            ),
+            (self.key(Action::ZoomAuto), "auto tile size"),
+            (self.key(Action::Theme), "change theme"),
+            (self.key(Action::Help), "toggle help"),
+            (self.key(Action::Quit), "quit game"),
+        ];
+        let key_width = controls
+            .iter()
+            .map(|(key, _)| key.chars().count() + 2)
+            .max()
+            .unwrap_or_default()
+            .max(8);
+
+        let mut wrapper = Layout::vertical().center();
+        for (key, action) in controls {
+            if key.trim().is_empty() {
+                continue;
+            }
+            wrapper.add_child(
+                self.render_control(&key, action, key_width),
+                Constrain::Length(1),
+            );
+        }
+        wrapper.add_child(Spacer::new(), Constrain::Length(1));
         wrapper.add_child(
-            "🛈 Press i for help".fg(Fg::Hex(0x303030)),
+            format!("Seed: {}", self.board.seed()),
             Constrain::Length(1),
         );
 
         let mut main = Layout::horizontal().center();
-        main.add_child(wrapper, Constrain::Length(self.board.width()));
+        main.add_child(wrapper, Constrain::Length(key_width + 18));
 
         _ = self.term.render(main);
     }
 
-    /// Renders the help screen
-    fn render_help(&self) {
+    /// Renders the screen offering to resume saved game
+    fn render_resume(&self) {
         let mut wrapper = Layout::vertical().center();
+        wrapper.add_child("Saved game found", Constrain::Length(1));
+        if let Some(save) = &self.saved {
+            wrapper.add_child(
+                format!("Score: {}", save.board.score),
+                Constrain::Length(1),
+            );
+        }
+        wrapper.add_child(Spacer::new(), Constrain::Length(1));
+        wrapper.add_child(
+            self.render_control("y", "resume game", 8),
+            Constrain::Length(1),
+        );
         wrapper.add_child(
-            self.render_control("←↑↓→", "tiles movement"),
+            self.render_control("n", "new game", 8),
             Constrain::Length(1),
         );
+
+        let mut main = Layout::horizontal().center();
+        main.add_child(wrapper, Constrain::Length(22));
+
+        _ = self.term.render(main);
+    }
+
+    /// Renders the screen informing that saved game couldn't be loaded
+    fn render_load_error(&self, msg: &str) {
+        let mut wrapper = Layout::vertical().center();
+        wrapper.add_child(
+            "Saved game couldn't be loaded:".fg(self.renderer.error()),
+            Constrain::Length(1),
+        );
+        wrapper.add_child(msg, Constrain::Min(1));
+        wrapper.add_child(Spacer::new(), Constrain::Length(1));
+        wrapper
+            .add_child("Press any key to start new game", Constrain::Length(1));
+
+        let mut main = Layout::horizontal().center();
+        main.add_child(wrapper, Constrain::Length(32));
+
+        _ = self.term.render(main);
+    }
+
+    /// Renders the high-scores screen
+    fn render_scores(&self) {
+        let mut wrapper = Layout::vertical().center();
         wrapper.add_child(
-            self.render_control("r", "restart game"),
+            "High scores".fg(self.renderer.accent()),
             Constrain::Length(1),
         );
+
+        if let Some(table) = self.scores.tables().get(self.scores_table) {
+            wrapper.add_child(
+                format!(
+                    "{} {} ({}/{}) {}",
+                    self.symbol("◀", "<"),
+                    table.key,
+                    self.scores_table + 1,
+                    self.scores.tables().len(),
+                    self.symbol("▶", ">"),
+                ),
+                Constrain::Length(1),
+            );
+            wrapper.add_child(Spacer::new(), Constrain::Length(1));
+            wrapper.add_child(
+                RawSpan::new(format!(
+                    "{:>2} {:>8} {:>6} {:>6} {:>8}  {}",
+                    "#", "Score", "Tile", "Moves", "Time", "Date"
+                )),
+                Constrain::Length(1),
+            );
+            for (i, record) in table.records.iter().enumerate() {
+                let secs = record.duration.as_secs();
+                let line = format!(
+                    "{:>2} {:>8} {:>6} {:>6} {:>8}  {}",
+                    i + 1,
+                    record.score,
+                    record.max_tile,
+                    record.moves,
+                    format!(
+                        "{}:{:02}:{:02}",
+                        secs / 3600,
+                        secs / 60 % 60,
+                        secs % 60
+                    ),
+                    record.date.format("%Y-%m-%d"),
+                );
+                let fg = if self.last_record == Some((self.scores_table, i)) {
+                    self.renderer.highlight()
+                } else {
+                    Fg::Default
+                };
+                wrapper
+                    .add_child(RawSpan::new(line).fg(fg), Constrain::Length(1));
+            }
+        } else {
+            wrapper.add_child("No scores yet", Constrain::Length(1));
+        }
+
+        if let Some(e) = &self.scores_error {
+            wrapper.add_child(Spacer::new(), Constrain::Length(1));
+            wrapper.add_child(
+                e.as_str().fg(self.renderer.error()),
+                Constrain::Min(1),
+            );
+        }
+
+        let mut main = Layout::horizontal().center();
+        main.add_child(wrapper, Constrain::Length(46));
+
+        _ = self.term.render(main);
+    }
+
+    /// Renders the replay screen
+    fn render_replay(&self) {
+        let Some(player) = &self.player else {
+            return;
+        };
+        let board = player.board();
+
+        let mut wrapper = Layout::vertical().center();
+        let left = format!("Replay: {}/{}", player.pos(), player.len());
+        let state = if player.is_playing() {
+            self.symbol("▶", ">")
+        } else {
+            self.symbol("⏸", "||")
+        };
+        let right = format!("{state} {}x", player.speed());
         wrapper.add_child(
-            self.render_control("i", "toggle help"),
+            self.status_line(board, &left, &right),
             Constrain::Length(1),
         );
+        let widget = match &self.animation {
+            Some(anim) => self.renderer.animation(board, anim),
+            None => self.renderer.board(board),
+        };
         wrapper.add_child(
-            self.render_control("Esc/q", "quit game"),
+            widget,
+            Constrain::Length(self.renderer.board_height(board)),
+        );
+        wrapper.add_child(
+            RawSpan::new(format!(
+                "Space{}, {} step, {} back",
+                self.symbol(" ⏯", ""),
+                self.keys_or(&[Action::Left, Action::Right], "←→", "<>"),
+                self.key(Action::Replay)
+            ))
+            .fg(self.renderer.hint()),
             Constrain::Length(1),
         );
 
         let mut main = Layout::horizontal().center();
-        main.add_child(wrapper, Constrain::Length(22));
+        main.add_child(
+            wrapper,
+            Constrain::Length(self.renderer.board_width(board)),
+        );
 
         _ = self.term.render(main);
     }
 
     /// Handles key listening of the game screen
     fn game_listener(&mut self, code: KeyCode) -> Result<(), Error> {
-        match code {
-            KeyCode::Up => self.status = self.board.up(),
-            KeyCode::Down => self.status = self.board.down(),
-            KeyCode::Left => self.status = self.board.left(),
-            KeyCode::Right => self.status = self.board.right(),
-            KeyCode::Char('r') => {
-                self.board.reset();
-                self.status = GameStatus::Playing;
+        // Message can be wider than the board, so it has to be cleared
+        if self.message.take().is_some() {
+            print!("\x1b[H\x1b[J");
+        }
+        if let Some(action) = self.pending.take() {
+            if code == KeyCode::Char('y') {
+                return self.game_action(action);
+            }
+            self.render();
+            return Ok(());
+        }
+
+        let Some(action) = self.keys.action(code) else {
+            return Ok(());
+        };
+        let confirm = match action {
+            Action::Quit => self.confirm_quit,
+            Action::Restart => self.confirm_restart,
+            _ => false,
+        };
+        if confirm {
+            self.pending = Some(action);
+            let question = match action {
+                Action::Quit => "Quit game?",
+                _ => "Restart game?",
+            };
+            self.message = Some(format!("{question} y/n"));
+            self.render();
+            return Ok(());
+        }
+        self.game_action(action)
+    }
+
+    /// Does the [`Action`] on the game screen
+    fn game_action(&mut self, action: Action) -> Result<(), Error> {
+        match action {
+            Action::Up => self.make_move(Direction::Up),
+            Action::Down => self.make_move(Direction::Down),
+            Action::Left => self.make_move(Direction::Left),
+            Action::Right => self.make_move(Direction::Right),
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::KeepGoing if self.status == GameStatus::Victory => {
+                self.board.keep_going();
+                self.status = self.board.status();
+                print!("\x1b[H\x1b[J");
+            }
+            Action::KeepGoing => return Ok(()),
+            Action::Hint => {
+                self.hint = self.solver.hint(&self.board, self.hint_time)
+            }
+            Action::Autoplay => {
+                self.autoplay = !self.autoplay;
+                self.next_ai = Instant::now();
+                print!("\x1b[H\x1b[J");
+            }
+            Action::Slower => {
+                self.ai_delay = (self.ai_delay * 2).min(MAX_AI_DELAY)
+            }
+            Action::Faster => {
+                self.ai_delay = (self.ai_delay / 2).max(MIN_AI_DELAY)
+            }
+            Action::Restart => {
+                self.restart();
+                print!("\x1b[H\x1b[J");
+            }
+            Action::Scores => {
+                let key = ScoreKey::new(&self.board);
+                self.scores_table = self
+                    .scores
+                    .tables()
+                    .iter()
+                    .position(|t| t.key == key)
+                    .unwrap_or_default();
+                self.screen = Screen::Scores;
                 print!("\x1b[H\x1b[J");
             }
-            KeyCode::Char('i') => {
+            Action::Help => {
                 self.screen = Screen::Help;
                 print!("\x1b[H\x1b[J");
             }
-            KeyCode::Char('q') | KeyCode::Esc => return Err(Error::Exit),
+            Action::Replay => self.open_replay(),
+            Action::SaveReplay => self.store_replay(),
+            Action::Theme => self.next_theme(),
+            Action::ZoomIn => {
+                self.zoom(Some(self.renderer.tile_size().larger()))
+            }
+            Action::ZoomOut => {
+                self.zoom(Some(self.renderer.tile_size().smaller()))
+            }
+            Action::ZoomAuto => self.zoom(None),
+            Action::Quit => return Err(Error::Exit),
+        }
+
+        self.render();
+        Ok(())
+    }
+
+    /// Makes move in given direction and saves state before it to history
+    fn make_move(&mut self, dir: Direction) {
+        if self.status == GameStatus::Victory {
+            return;
+        }
+        self.hint = None;
+        let snapshot = self.board.snapshot();
+        let tiles = self.board.tiles().to_vec();
+        let outcome = self.board.shift(dir);
+        self.status = outcome.status;
+        if !outcome.changed {
+            return;
+        }
+        self.history.push(snapshot);
+        let played = self.played();
+        if let Some(replay) = &mut self.replay {
+            replay.push(dir, played);
+        }
+        self.undone.clear();
+        if self.status == GameStatus::GameOver {
+            self.record_score();
+        }
+
+        // Autoplay shouldn't wait for the animation
+        let time = if self.autoplay {
+            self.anim_time.min(self.ai_delay)
+        } else {
+            self.anim_time
+        };
+        if !time.is_zero() {
+            self.animation = Some(Animation::new(tiles, outcome, time));
+        }
+    }
+
+    /// Undoes the last move
+    fn undo(&mut self) {
+        self.hint = None;
+        if let Some(snapshot) = self.history.undo(self.board.snapshot()) {
+            self.board.restore(snapshot);
+            self.status = self.board.status();
+            // Game continues, so its result is recorded again when it ends
+            if self.status != GameStatus::GameOver {
+                self.unrecord_score();
+            }
+            if let Some(mov) = self.replay.as_mut().and_then(|r| r.pop()) {
+                self.undone.push(mov);
+            }
+        }
+    }
+
+    /// Redoes the last undone move
+    fn redo(&mut self) {
+        self.hint = None;
+        if let Some(snapshot) = self.history.redo(self.board.snapshot()) {
+            self.board.restore(snapshot);
+            self.status = self.board.status();
+            if let (Some(replay), Some((dir, time))) =
+                (&mut self.replay, self.undone.pop())
+            {
+                replay.push(dir, time);
+            }
+            if self.status == GameStatus::GameOver {
+                self.record_score();
+            }
+        }
+    }
+
+    /// Opens replay screen playing back the current game
+    fn open_replay(&mut self) {
+        let Some(replay) = &self.replay else {
+            self.message = Some("No replay".to_string());
+            return;
+        };
+        match Player::new(replay.clone()) {
+            Ok(player) => {
+                self.player = Some(player);
+                self.screen = Screen::Replay;
+                print!("\x1b[H\x1b[J");
+            }
+            Err(_) => self.message = Some("Invalid replay".to_string()),
+        }
+    }
+
+    /// Saves replay of the current game to the replays directory
+    fn store_replay(&mut self) {
+        let msg = match self.replay.as_ref().map(replays::store) {
+            Some(Ok(_)) => "Replay saved",
+            Some(Err(_)) => "Replay not saved",
+            None => "No replay",
+        };
+        self.message = Some(msg.to_string());
+    }
+
+    /// Switches to the next theme
+    fn next_theme(&mut self) {
+        self.theme = (self.theme + 1) % self.themes.len();
+        let theme = self.themes[self.theme].clone();
+        self.message = Some(format!("Theme: {}", theme.name));
+        self.renderer.set_theme(theme);
+    }
+
+    /// Plays the next move of the replay
+    fn replay_step(&mut self) {
+        let Some(player) = &mut self.player else {
+            return;
+        };
+        let tiles = player.board().tiles().to_vec();
+        if let Some(outcome) = player.step() {
+            let time = self.anim_time.min(player.delay());
+            if !time.is_zero() {
+                self.animation = Some(Animation::new(tiles, outcome, time));
+            }
+        }
+        self.render();
+    }
+
+    /// Handles key listening of the replay screen
+    fn replay_listener(&mut self, code: KeyCode) -> Result<(), Error> {
+        let Some(player) = &mut self.player else {
+            return Ok(());
+        };
+        match (code, self.keys.action(code)) {
+            (KeyCode::Char(' '), _) => player.toggle(),
+            (_, Some(Action::Quit)) => return Err(Error::Exit),
+            (_, Some(Action::Right)) => {
+                if player.is_playing() {
+                    player.toggle();
+                }
+                self.replay_step();
+                return Ok(());
+            }
+            (_, Some(Action::Left)) => {
+                if player.is_playing() {
+                    player.toggle();
+                }
+                player.back();
+            }
+            (_, Some(Action::Slower)) => player.slower(),
+            (_, Some(Action::Faster)) => player.faster(),
+            (_, Some(Action::ZoomIn)) => {
+                self.zoom = Some(self.renderer.tile_size().larger())
+            }
+            (_, Some(Action::ZoomOut)) => {
+                self.zoom = Some(self.renderer.tile_size().smaller())
+            }
+            (_, Some(Action::ZoomAuto)) => self.zoom = None,
+            (_, Some(Action::Replay)) => {
+                self.player = None;
+                self.screen = Screen::Game;
+                print!("\x1b[H\x1b[J");
+            }
+            _ => return Ok(()),
+        }
+
+        self.render();
+        Ok(())
+    }
+
+    /// Handles key listening of the resume screen
+    fn resume_listener(&mut self, code: KeyCode) -> Result<(), Error> {
+        match code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(save) = self.saved.take() {
+                    self.load_save(save);
+                }
+            }
+            KeyCode::Char('n') => self.saved = None,
+            _ if self.keys.action(code) == Some(Action::Quit) => {
+                return Err(Error::Exit)
+            }
             _ => return Ok(()),
         }
 
+        print!("\x1b[H\x1b[J");
+        self.screen = Screen::Game;
+        self.render();
+        Ok(())
+    }
+
+    /// Handles key listening of the load error screen
+    fn load_error_listener(&mut self, code: KeyCode) -> Result<(), Error> {
+        if self.keys.action(code) == Some(Action::Quit) {
+            return Err(Error::Exit);
+        }
+
+        print!("\x1b[H\x1b[J");
+        self.screen = Screen::Game;
+        self.render();
+        Ok(())
+    }
+
+    /// Handles key listening of the scores screen
+    fn scores_listener(&mut self, code: KeyCode) -> Result<(), Error> {
+        let len = self.scores.tables().len().max(1);
+        match (code, self.keys.action(code)) {
+            (_, Some(Action::Quit)) => return Err(Error::Exit),
+            (_, Some(Action::Left)) => {
+                self.scores_table = (self.scores_table + len - 1) % len
+            }
+            (_, Some(Action::Right)) => {
+                self.scores_table = (self.scores_table + 1) % len
+            }
+            (_, Some(Action::Scores)) => self.screen = Screen::Game,
+            _ => return Ok(()),
+        }
+
+        print!("\x1b[H\x1b[J");
         self.render();
         Ok(())
     }
 
     /// Handles key listening of the help screen
     fn help_listener(&mut self, code: KeyCode) -> Result<(), Error> {
-        match code {
-            KeyCode::Esc | KeyCode::Char('q') => return Err(Error::Exit),
-            KeyCode::Char('i') => {
+        match self.keys.action(code) {
+            Some(Action::Quit) => return Err(Error::Exit),
+            Some(Action::Help) => {
                 print!("\x1b[H\x1b[J");
                 self.screen = Screen::Game
             }
@@ -173,22 +1147,88 @@ impl App {
         Ok(())
     }
 
-    fn render_status(&self) -> Layout {
-        let mut status = Layout::horizontal();
-        status.add_child(
-            format!("Score: {}", self.board.score),
-            Constrain::Min(0),
-        );
-        status.add_child(Spacer::new(), Constrain::Fill);
-        status.add_child(self.status.to_string(), Constrain::Min(0));
-        status
+    /// Renders status line, it's padded to the board width, so it always
+    /// overwrites the previous one
+    fn render_status(&self) -> RawSpan {
+        let mut left = format!("Score: {}", self.board.score);
+        if self.history.undos() > 0 {
+            left.push_str(&format!(" Undos: {}", self.history.undos()));
+        }
+        let right = match (&self.message, self.hint, self.status) {
+            (Some(msg), _, _) => msg.clone(),
+            (_, Some(hint), _) => {
+                let dir = match self.renderer.is_ascii() {
+                    true => hint.dir.letter(),
+                    false => hint.dir.arrow(),
+                };
+                format!("Hint: {dir} +{}", hint.gain)
+            }
+            (_, _, GameStatus::Playing) => {
+                format!("Target: {}", self.board.target())
+            }
+            (_, _, status) => status.to_string(),
+        };
+        self.status_line(&self.board, &left, &right)
+    }
+
+    /// Gets status line with the left and right part, padded to the width
+    /// of the given board
+    fn status_line(&self, board: &Board, left: &str, right: &str) -> RawSpan {
+        let pad = self
+            .renderer
+            .board_width(board)
+            .saturating_sub(left.chars().count())
+            .max(right.chars().count() + 1);
+        RawSpan::new(format!("{left}{right:>pad$}"))
+    }
+
+    /// Picks the unicode text or its ASCII replacement in the ASCII mode
+    fn symbol<'a>(&self, unicode: &'a str, ascii: &'a str) -> &'a str {
+        if self.renderer.is_ascii() {
+            ascii
+        } else {
+            unicode
+        }
+    }
+
+    /// Gets names of the keys bound to the [`Action`]
+    fn key(&self, action: Action) -> String {
+        self.keys.describe(action, self.renderer.is_ascii())
+    }
+
+    /// Gets the unicode text or its ASCII replacement when the actions are
+    /// bound to the default keys, otherwise names of the bound keys
+    fn keys_or(
+        &self,
+        actions: &[Action],
+        unicode: &str,
+        ascii: &str,
+    ) -> String {
+        let defaults = Keys::default();
+        if actions
+            .iter()
+            .all(|a| self.keys.keys(*a).eq(defaults.keys(*a)))
+        {
+            self.symbol(unicode, ascii).to_string()
+        } else {
+            actions
+                .iter()
+                .map(|a| self.key(*a))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
     }
 
-    fn render_control(&self, key: &str, action: &str) -> Layout {
+    fn render_control(
+        &self,
+        key: &str,
+        action: &str,
+        key_width: usize,
+    ) -> Layout {
         let mut control = Layout::horizontal();
         control.add_child(
-            RawSpan::new(format!("{key}:")).fg(Fg::Cyan),
-            Constrain::Length(8),
+            RawSpan::new(format!("{key}:")).fg(self.renderer.accent()),
+            Constrain::Length(key_width),
         );
         control.add_child(action, Constrain::Fill);
         control
@@ -199,8 +1239,38 @@ impl Default for App {
     fn default() -> Self {
         Self {
             board: Default::default(),
+            history: Default::default(),
             status: GameStatus::Playing,
             screen: Screen::Game,
+            saved: None,
+            scores: Default::default(),
+            scores_error: None,
+            scores_table: 0,
+            last_record: None,
+            recorded: false,
+            played: Duration::ZERO,
+            since: Instant::now(),
+            renderer: Renderer::new(ColorMode::TrueColor),
+            solver: Solver::default(),
+            autoplay: false,
+            ai_delay: DEFAULT_AI_DELAY,
+            next_ai: Instant::now(),
+            hint: None,
+            hint_time: DEFAULT_HINT_TIME,
+            replay: None,
+            undone: vec![],
+            player: None,
+            keep_save: false,
+            message: None,
+            animation: None,
+            anim_time: DEFAULT_ANIM_TIME,
+            zoom: None,
+            keys: Keys::default(),
+            confirm_quit: false,
+            confirm_restart: false,
+            pending: None,
+            themes: Theme::builtin(),
+            theme: 0,
             term: Term::new(),
         }
     }
@@ -0,0 +1,252 @@
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use termint::{enums::fg::Fg, widgets::span::StrSpanExtension};
+use tui2048::{ai, board::DEFAULT_TARGET, history::DEFAULT_DEPTH, tile::Tile};
+
+use crate::{
+    app::{DEFAULT_AI_DELAY, DEFAULT_ANIM_TIME, DEFAULT_HINT_TIME},
+    cli,
+    color_mode::ColorMode,
+    error::Error,
+};
+
+/// Parsed command line arguments
+#[derive(Debug)]
+pub struct Args {
+    pub width: usize,
+    pub height: usize,
+    pub seed: Option<u64>,
+    pub target: Tile,
+    pub load: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    /// Color mode, detected from the terminal when not set
+    pub color: Option<ColorMode>,
+    /// Name of built-in or user theme, or path to a theme file
+    pub theme: Option<String>,
+    pub ascii: bool,
+    pub undo_depth: usize,
+    pub ai_depth: usize,
+    pub ai_delay: Duration,
+    pub hint_time: Duration,
+    pub anim_time: Duration,
+    /// Config file used instead of the one in the config directory
+    pub config: Option<PathBuf>,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    /// Parses arguments from the given iterator (without program name)
+    pub fn parse<I>(args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self::default().update(args)
+    }
+
+    /// Updates the [`Args`] by arguments from the given iterator (without
+    /// program name)
+    pub fn update<I>(self, args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut res = self;
+        // Splits `--flag=value` into flag and value
+        let mut args =
+            args.into_iter().flat_map(|arg| match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    vec![flag.to_string(), value.to_string()]
+                }
+                _ => vec![arg],
+            });
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => res.help = true,
+                "-v" | "--version" => res.version = true,
+                "--width" => {
+                    res.width = Self::parse_size(&arg, args.next())?;
+                }
+                "--height" => {
+                    res.height = Self::parse_size(&arg, args.next())?;
+                }
+                "-s" | "--seed" => {
+                    res.seed = Some(Self::parse_value(&arg, args.next())?)
+                }
+                "-t" | "--target" => {
+                    res.target = Self::parse_target(&arg, args.next())?;
+                }
+                "-l" | "--load" => {
+                    res.load = Some(Self::parse_value(&arg, args.next())?)
+                }
+                "-r" | "--replay" => {
+                    res.replay = Some(Self::parse_value(&arg, args.next())?)
+                }
+                "-c" | "--color" => {
+                    res.color = Some(Self::parse_value(&arg, args.next())?)
+                }
+                "--no-color" => res.color = Some(ColorMode::Never),
+                "--ascii" => res.ascii = true,
+                "--theme" => {
+                    res.theme = Some(Self::parse_value(&arg, args.next())?)
+                }
+                "-u" | "--undo-depth" => {
+                    res.undo_depth = Self::parse_value(&arg, args.next())?
+                }
+                "--ai-depth" => {
+                    res.ai_depth = Self::parse_depth(&arg, args.next())?
+                }
+                "--hint-time" => {
+                    res.hint_time = Duration::from_millis(Self::parse_value(
+                        &arg,
+                        args.next(),
+                    )?)
+                }
+                "--ai-delay" => {
+                    res.ai_delay = Duration::from_millis(Self::parse_value(
+                        &arg,
+                        args.next(),
+                    )?)
+                }
+                "--animation" => {
+                    res.anim_time = Duration::from_millis(Self::parse_value(
+                        &arg,
+                        args.next(),
+                    )?)
+                }
+                "--no-animation" => res.anim_time = Duration::ZERO,
+                "--config" => {
+                    res.config = Some(Self::parse_value(&arg, args.next())?)
+                }
+                _ => {
+                    return Err(Error::Args(format!(
+                        "invalid argument '{arg}'"
+                    )))
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Prints help
+    pub fn help() {
+        println!(
+            "Welcome to help for {} by {}\n",
+            "tui2048".fg(Fg::Green),
+            "Martan03".fg(Fg::Cyan)
+        );
+        println!("{}", "Usage:".fg(Fg::Green));
+        println!("  {} [{}]\n", "tui2048".fg(Fg::White), "flags".fg(Fg::Gray));
+        println!("{}", "Flags:".fg(Fg::Green));
+        Self::help_flag("-h  --help", "Prints this help");
+        Self::help_flag("-v  --version", "Prints version");
+        Self::help_flag("    --width <columns>", "Sets board width [4]");
+        Self::help_flag("    --height <rows>", "Sets board height [4]");
+        Self::help_flag("-s  --seed <seed>", "Starts game with given seed");
+        Self::help_flag(
+            "-t  --target <tile>",
+            "Sets tile needed for victory [2048]",
+        );
+        Self::help_flag("-l  --load <file>", "Loads game from save file");
+        Self::help_flag("-r  --replay <file>", "Plays back replay file");
+        Self::help_flag(
+            "-c  --color <mode>",
+            "Sets colors: truecolor, 256, 16 or never [detected]",
+        );
+        Self::help_flag("    --no-color", "Renders the game without colors");
+        Self::help_flag("    --ascii", "Draws the game using only ASCII");
+        Self::help_flag(
+            "    --theme <name|file>",
+            "Sets color theme by name or theme file [classic]",
+        );
+        Self::help_flag(
+            "-u  --undo-depth <moves>",
+            &format!("Sets how many moves can be undone [{DEFAULT_DEPTH}]"),
+        );
+        Self::help_flag(
+            "    --ai-depth <moves>",
+            &format!(
+                "Sets how many moves AI looks ahead [{}]",
+                ai::DEFAULT_DEPTH
+            ),
+        );
+        Self::help_flag(
+            "    --ai-delay <ms>",
+            &format!(
+                "Sets delay between autoplay moves [{}]",
+                DEFAULT_AI_DELAY.as_millis()
+            ),
+        );
+        Self::help_flag(
+            "    --hint-time <ms>",
+            &format!(
+                "Sets time limit for searching hint [{}]",
+                DEFAULT_HINT_TIME.as_millis()
+            ),
+        );
+        Self::help_flag(
+            "    --animation <ms>",
+            &format!(
+                "Sets duration of move animation [{}]",
+                DEFAULT_ANIM_TIME.as_millis()
+            ),
+        );
+        Self::help_flag("    --no-animation", "Disables move animation");
+        Self::help_flag(
+            "    --config <file>",
+            "Loads settings from given config file",
+        );
+    }
+
+    /// Prints single flag with its description
+    fn help_flag(flag: &str, description: &str) {
+        let flag = format!("{flag:<26}");
+        println!("  {}{description}", flag.as_str().fg(Fg::Yellow));
+    }
+
+    /// Parses board dimension, see [`cli::parse_size`]
+    fn parse_size(arg: &str, value: Option<String>) -> Result<usize, Error> {
+        cli::parse_size(arg, value).map_err(Error::Args)
+    }
+
+    /// Parses depth of the AI search, see [`cli::parse_depth`]
+    fn parse_depth(arg: &str, value: Option<String>) -> Result<usize, Error> {
+        cli::parse_depth(arg, value).map_err(Error::Args)
+    }
+
+    /// Parses victory tile, which has to be power of two greater than 2
+    fn parse_target(arg: &str, value: Option<String>) -> Result<Tile, Error> {
+        cli::parse_target(arg, value).map_err(Error::Args)
+    }
+
+    /// Parses value of the given argument
+    fn parse_value<T>(arg: &str, value: Option<String>) -> Result<T, Error>
+    where
+        T: FromStr,
+    {
+        cli::parse_value(arg, value).map_err(Error::Args)
+    }
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            width: 4,
+            height: 4,
+            seed: None,
+            target: DEFAULT_TARGET,
+            load: None,
+            replay: None,
+            color: None,
+            theme: None,
+            ascii: false,
+            undo_depth: DEFAULT_DEPTH,
+            ai_depth: ai::DEFAULT_DEPTH,
+            ai_delay: DEFAULT_AI_DELAY,
+            hint_time: DEFAULT_HINT_TIME,
+            anim_time: DEFAULT_ANIM_TIME,
+            config: None,
+            help: false,
+            version: false,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// Writes the value as JSON to the given file. It's written to a temporary
+/// file first, which then replaces the given one, so the file is never left
+/// half written
+pub fn write_json<T>(path: &Path, value: &T) -> Result<(), Error>
+where
+    T: Serialize,
+{
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    let mut file = BufWriter::new(File::create(&tmp)?);
+    serde_json::to_writer(&mut file, value).map_err(io::Error::from)?;
+    file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use std::sync::OnceLock;
+
+use crate::{direction::Direction, tile::Tile};
+
+/// Highest tile exponent stored in the [`BitBoard`]
+pub const MAX_EXP: u8 = 15;
+
+/// 4x4 board stored in single 64-bit number, each tile takes 4 bits and
+/// contains its exponent. Moves are made using precomputed tables of all
+/// the possible rows, which is much faster than moving the separate tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitBoard(u64);
+
+/// Results of moving every possible row to the left and to the right
+struct Tables {
+    left: Vec<u16>,
+    right: Vec<u16>,
+    /// Score gained by moving the row, it's the same for both directions
+    /// when the row is reversed
+    score: Vec<u32>,
+    /// Where the tiles of the row move when moving to the left, see
+    /// [`BitBoard::line_moves`]
+    moves: Vec<u16>,
+}
+
+impl BitBoard {
+    /// Creates [`BitBoard`] from the tiles of 4x4 board row by row. Returns
+    /// [`None`] when there isn't 16 tiles or any of the tiles is so high,
+    /// that it could merge above [`MAX_EXP`]
+    pub fn from_tiles(tiles: &[Tile]) -> Option<Self> {
+        if tiles.len() != 16 {
+            return None;
+        }
+        let mut bits = 0;
+        for (pos, tile) in tiles.iter().enumerate() {
+            if tile.exp() >= MAX_EXP {
+                return None;
+            }
+            bits |= (tile.exp() as u64) << (pos * 4);
+        }
+        Some(Self(bits))
+    }
+
+    /// Writes the tiles to the given slice of 16 tiles row by row
+    pub fn write_tiles(&self, tiles: &mut [Tile]) {
+        for (pos, tile) in tiles.iter_mut().enumerate() {
+            *tile = Tile::from_exp(self.get(pos));
+        }
+    }
+
+    /// Gets exponent of the tile on the given position
+    pub fn get(&self, pos: usize) -> u8 {
+        (self.0 >> (pos * 4) & 0xf) as u8
+    }
+
+    /// Sets exponent of the tile on the given position
+    pub fn set(&mut self, pos: usize, exp: u8) {
+        let shift = pos * 4;
+        self.0 = self.0 & !(0xf << shift) | ((exp as u64 & 0xf) << shift);
+    }
+
+    /// Gets the highest tile exponent
+    pub fn max_exp(&self) -> u8 {
+        (0..16).map(|pos| self.get(pos)).max().unwrap_or_default()
+    }
+
+    /// Gets the rows as 16-bit numbers, the first tile of the row is in the
+    /// lowest 4 bits
+    pub fn rows(&self) -> [u16; 4] {
+        [0, 1, 2, 3].map(|y| self.row(y))
+    }
+
+    /// Gets the columns as 16-bit numbers, the top tile of the column is
+    /// in the lowest 4 bits
+    pub fn cols(&self) -> [u16; 4] {
+        Self(Self::transpose(self.0)).rows()
+    }
+
+    /// Gets the rows or columns as 16-bit numbers, where the tiles are
+    /// ordered starting from the edge the tiles are moving to
+    pub fn lines(&self, dir: Direction) -> [u16; 4] {
+        match dir {
+            Direction::Up => self.cols(),
+            Direction::Down => self.cols().map(Self::reverse),
+            Direction::Left => self.rows(),
+            Direction::Right => self.rows().map(Self::reverse),
+        }
+    }
+
+    /// Gets where the tiles of the line move, when the line is moved to its
+    /// start. For each tile it contains its new position in the line and
+    /// whether it merges with the tile already there
+    pub fn line_moves(line: u16) -> [(usize, bool); 4] {
+        let moves = Self::tables().moves[line as usize];
+        [0, 1, 2, 3].map(|i| {
+            (
+                (moves >> (i * 2) & 0b11) as usize,
+                moves >> (8 + i) & 1 == 1,
+            )
+        })
+    }
+
+    /// Moves the tiles in given direction without generating new tile.
+    /// Returns score gained by the move. Tiles with [`MAX_EXP`] never merge
+    pub fn slide(&mut self, dir: Direction) -> u32 {
+        let tables = Self::tables();
+        let (bits, table) = match dir {
+            Direction::Up => (Self::transpose(self.0), &tables.left),
+            Direction::Down => (Self::transpose(self.0), &tables.right),
+            Direction::Left => (self.0, &tables.left),
+            Direction::Right => (self.0, &tables.right),
+        };
+
+        let mut res = 0;
+        let mut score = 0;
+        for y in 0..4 {
+            let row = (bits >> (y * 16) & 0xffff) as u16;
+            res |= (table[row as usize] as u64) << (y * 16);
+            score += match dir {
+                Direction::Up | Direction::Left => tables.score[row as usize],
+                _ => tables.score[Self::reverse(row) as usize],
+            };
+        }
+
+        self.0 = match dir {
+            Direction::Up | Direction::Down => Self::transpose(res),
+            _ => res,
+        };
+        score
+    }
+
+    /// Gets the row on the given index
+    fn row(&self, y: usize) -> u16 {
+        (self.0 >> (y * 16) & 0xffff) as u16
+    }
+
+    /// Swaps rows with columns
+    fn transpose(bits: u64) -> u64 {
+        let a1 = bits & 0xf0f0_0f0f_f0f0_0f0f;
+        let a2 = bits & 0x0000_f0f0_0000_f0f0;
+        let a3 = bits & 0x0f0f_0000_0f0f_0000;
+        let a = a1 | (a2 << 12) | (a3 >> 12);
+        let b1 = a & 0xff00_ff00_00ff_00ff;
+        let b2 = a & 0x00ff_00ff_0000_0000;
+        let b3 = a & 0x0000_0000_ff00_ff00;
+        b1 | (b2 >> 24) | (b3 << 24)
+    }
+
+    /// Reverses order of the tiles in the row
+    fn reverse(row: u16) -> u16 {
+        (row >> 12) | (row >> 4 & 0x00f0) | (row << 4 & 0x0f00) | (row << 12)
+    }
+
+    /// Gets the move tables, they're computed on the first use
+    fn tables() -> &'static Tables {
+        static TABLES: OnceLock<Tables> = OnceLock::new();
+        TABLES.get_or_init(|| {
+            let mut tables = Tables {
+                left: vec![0; 1 << 16],
+                right: vec![0; 1 << 16],
+                score: vec![0; 1 << 16],
+                moves: vec![0; 1 << 16],
+            };
+            for row in 0..=u16::MAX {
+                let (left, score, moves) = Self::slide_row(row);
+                tables.left[row as usize] = left;
+                tables.score[row as usize] = score;
+                tables.moves[row as usize] = moves;
+                tables.right[Self::reverse(row) as usize] = Self::reverse(left);
+            }
+            tables
+        })
+    }
+
+    /// Moves the row to the left, merging each tile at most once. Returns
+    /// the moved row, the gained score and where the tiles moved, which has
+    /// 2 bits with the new position of each tile followed by 4 bits with
+    /// flags of the merged tiles
+    fn slide_row(row: u16) -> (u16, u32, u16) {
+        let mut res = [0; 4];
+        let mut score = 0;
+        let mut moves = 0;
+        let mut next = 0;
+        let mut mergeable = false;
+        for i in 0..4 {
+            moves |= (i as u16) << (i * 2);
+            let exp = (row >> (i * 4) & 0xf) as u8;
+            if exp == 0 {
+                continue;
+            }
+            moves &= !(0b11 << (i * 2));
+            if mergeable && res[next - 1] == exp && exp < MAX_EXP {
+                res[next - 1] += 1;
+                score += 1 << res[next - 1];
+                moves |= ((next - 1) as u16) << (i * 2) | 1 << (8 + i);
+                mergeable = false;
+                continue;
+            }
+            res[next] = exp;
+            moves |= (next as u16) << (i * 2);
+            mergeable = true;
+            next += 1;
+        }
+
+        let row = res
+            .iter()
+            .enumerate()
+            .fold(0, |row, (i, exp)| row | (*exp as u16) << (i * 4));
+        (row, score, moves)
+    }
+}
@@ -1,158 +1,265 @@
-use rand::{thread_rng, Rng};
-use termint::{
-    enums::{bg::Bg, fg::Fg},
-    geometry::constrain::Constrain,
-    widgets::layout::Layout,
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bitboard::BitBoard,
+    direction::Direction,
+    game_status::GameStatus,
+    outcome::{Merge, MoveOutcome, Slide, Spawn},
+    tile::Tile,
 };
 
-use crate::{game_status::GameStatus, raw_span::RawSpan, tile::Tile};
+/// Default tile needed for victory
+pub const DEFAULT_TARGET: Tile = Tile::from_exp(11);
+/// Chance that the generated tile is 4 instead of 2
+pub const FOUR_CHANCE: f64 = 0.1;
+/// Highest number of columns and rows of the [`Board`]
+pub const MAX_SIZE: usize = 64;
 
 /// Struct representing 2048 board
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Board {
     tiles: Vec<Tile>,
     pub score: usize,
+    #[serde(default)]
+    moves: usize,
     width: usize,
     height: usize,
+    #[serde(default = "default_target")]
+    target: Tile,
+    #[serde(default)]
+    keep_going: bool,
+    /// Chance that the generated tile is 4 instead of 2
+    #[serde(default = "default_four_chance")]
+    four_chance: f64,
+    spawned: Option<usize>,
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+/// Snapshot of the [`Board`] state, used for undo and redo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    tiles: Vec<Tile>,
+    score: usize,
+    moves: usize,
+    spawned: Option<usize>,
+    rng: ChaCha8Rng,
 }
 
 impl Board {
-    /// Creates new [`Board`]
+    /// Creates new [`Board`] with random seed
+    ///
+    /// # Panics
+    ///
+    /// Panics when width or height isn't between 2 and [`MAX_SIZE`]
     pub fn new(width: usize, height: usize) -> Self {
+        Self::with_seed(width, height, thread_rng().gen())
+    }
+
+    /// Creates new [`Board`] with given seed. Same seed and same moves
+    /// always result in the same game
+    ///
+    /// # Panics
+    ///
+    /// Panics when width or height isn't between 2 and [`MAX_SIZE`]
+    pub fn with_seed(width: usize, height: usize, seed: u64) -> Self {
+        assert!(
+            Self::is_valid_size(width) && Self::is_valid_size(height),
+            "board size has to be between 2 and {MAX_SIZE}, got \
+            {width}x{height}"
+        );
         let mut board = Self {
-            tiles: vec![Tile::new(0); width * height],
+            tiles: vec![Tile::EMPTY; width * height],
             score: 0,
+            moves: 0,
             width,
             height,
+            target: default_target(),
+            keep_going: false,
+            four_chance: FOUR_CHANCE,
+            spawned: None,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
         };
         board.generate();
         board
     }
 
-    /// Resets the [`Board`]
+    /// Sets value of the tile needed for victory
+    ///
+    /// # Panics
+    ///
+    /// Panics when the target isn't greater than 2
+    pub fn with_target(mut self, target: Tile) -> Self {
+        assert!(
+            target.is_valid_target(),
+            "target has to be greater than 2, got {target}"
+        );
+        self.target = target;
+        self
+    }
+
+    /// Sets chance that the generated tile is 4 instead of 2. The initial
+    /// tile is generated again, so the chance applies to it too
+    pub fn with_four_chance(mut self, chance: f64) -> Self {
+        self.four_chance = chance.clamp(0., 1.);
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+        self.tiles.fill(Tile::EMPTY);
+        self.generate();
+        self
+    }
+
+    /// Resets the [`Board`] and starts new game with random seed
     pub fn reset(&mut self) {
-        self.tiles = vec![Tile::new(0); self.width * self.height];
+        self.seed = thread_rng().gen();
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+        self.tiles = vec![Tile::EMPTY; self.width * self.height];
         self.score = 0;
+        self.moves = 0;
+        self.keep_going = false;
         self.generate();
     }
 
-    /// Gets [`Board`] as termint widget
-    pub fn get(&self) -> Layout {
-        let mut layout = Layout::vertical();
-        let mut cur = 0;
+    /// Gets seed of the current game
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
 
-        layout.add_child(
-            RawSpan::new("▂".repeat(6 * self.width + 1)).fg(Fg::Hex(0x797979)),
-            Constrain::Length(1),
-        );
-        for _ in 0..self.height {
-            let mut row = Layout::horizontal();
-            for _ in 0..self.width {
-                row.add_child(self.tiles[cur].get(), Constrain::Length(6));
-                cur += 1;
-            }
-            row.add_child(self.get_right_border(), Constrain::Length(1));
-            layout.add_child(row, Constrain::Length(3));
-        }
-        layout.add_child(
-            RawSpan::new("▔".repeat(6 * self.width + 1)).fg(Fg::Hex(0x797979)),
-            Constrain::Length(1),
-        );
+    /// Gets value of the tile needed for victory
+    pub fn target(&self) -> Tile {
+        self.target
+    }
 
-        layout
+    /// Gets chance that the generated tile is 4 instead of 2
+    pub fn four_chance(&self) -> f64 {
+        self.four_chance
     }
 
-    /// Gets width of the [`Board`]
-    pub fn width(&self) -> usize {
-        self.width * 6 + 1
+    /// Gets number of moves made in the current game
+    pub fn moves(&self) -> usize {
+        self.moves
     }
 
-    /// Gets height of the [`Board`]
-    pub fn height(&self) -> usize {
-        self.height * 3 + 2
+    /// Gets number of columns and rows of the [`Board`]
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
     }
 
-    /// Moves [`Board`] tiles up
-    pub fn up(&mut self) -> GameStatus {
-        let mut change = false;
-        for i in 0..self.width {
-            if self.move_up(i) {
-                change = true;
-            }
-        }
-        if change {
-            self.generate();
-        }
-        self.status()
+    /// Gets the highest tile on the [`Board`]
+    pub fn max_tile(&self) -> Tile {
+        self.tiles.iter().copied().max().unwrap_or(Tile::EMPTY)
     }
 
-    /// Moves [`Board`] tiles down
-    pub fn down(&mut self) -> GameStatus {
-        let mut change = false;
-        for i in (self.width * (self.height - 1))..self.tiles.len() {
-            if self.move_down(i) {
-                change = true;
-            }
-        }
-        if change {
-            self.generate();
-        }
-        self.status()
+    /// Checks whether the [`Board`] can have the given number of columns or
+    /// rows, which has to be between 2 and [`MAX_SIZE`]
+    pub fn is_valid_size(size: usize) -> bool {
+        (2..=MAX_SIZE).contains(&size)
     }
 
-    /// Moves [`Board`] tiles right
-    pub fn right(&mut self) -> GameStatus {
-        let mut change = false;
-        let mut cur = self.width - 1;
-        let offset = cur;
-        for _ in 0..self.height {
-            if self.move_right(cur, cur - offset) {
-                change = true;
-            }
-            cur += self.width;
-        }
-        if change {
-            self.generate();
+    /// Checks whether [`Board`] dimensions match its tiles, which might not
+    /// be true when it was deserialized from corrupted file
+    pub fn is_valid(&self) -> bool {
+        Self::is_valid_size(self.width)
+            && Self::is_valid_size(self.height)
+            && self.target.is_valid_target()
+            && self.tiles.len() == self.width * self.height
+            && self.spawned.is_none_or(|pos| pos < self.tiles.len())
+            && (0. ..=1.).contains(&self.four_chance)
+    }
+
+    /// Gets [`Snapshot`] of the current [`Board`] state
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            tiles: self.tiles.clone(),
+            score: self.score,
+            moves: self.moves,
+            spawned: self.spawned,
+            rng: self.rng.clone(),
         }
-        self.status()
     }
 
-    /// Moves [`Board`] tiles left
-    pub fn left(&mut self) -> GameStatus {
-        let mut change = false;
-        let mut cur = 0;
-        let offset = self.width - 1;
-        for _ in 0..self.height {
-            if self.move_left(cur, cur + offset) {
-                change = true;
-            }
-            cur += self.width;
+    /// Restores [`Board`] state from the given [`Snapshot`]
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.tiles = snapshot.tiles;
+        self.score = snapshot.score;
+        self.moves = snapshot.moves;
+        self.spawned = snapshot.spawned;
+        self.rng = snapshot.rng;
+    }
+
+    /// Moves [`Board`] tiles in given direction and generates new tile when
+    /// any tile moved
+    pub fn shift(&mut self, dir: Direction) -> MoveOutcome {
+        let mut outcome = MoveOutcome::new(GameStatus::Playing);
+        let score = self.score;
+        outcome.changed = self.move_tiles(dir, Some(&mut outcome));
+        if outcome.changed {
+            self.moves += 1;
+            outcome.spawn = Some(self.generate());
         }
-        if change {
-            self.generate();
+        outcome.score = self.score - score;
+        outcome.status = self.status();
+        outcome
+    }
+
+    /// Moves [`Board`] tiles in given direction without generating new tile.
+    /// Returns whether any tile was moved
+    pub fn slide(&mut self, dir: Direction) -> bool {
+        self.move_tiles(dir, None)
+    }
+
+    /// Gets the tiles as [`BitBoard`], [`None`] when the [`Board`] isn't 4x4
+    /// or its tiles are too high for the [`BitBoard`]
+    pub fn bitboard(&self) -> Option<BitBoard> {
+        if self.width != 4 || self.height != 4 {
+            return None;
         }
-        self.status()
+        BitBoard::from_tiles(&self.tiles)
     }
 
-    /// Generates new tile in empty space of [`Board`]
-    fn generate(&mut self) {
-        let mut rng = thread_rng();
+    /// Gets all the tiles of the [`Board`] row by row
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Sets tile on the given position
+    pub fn set_tile(&mut self, pos: usize, tile: Tile) {
+        self.tiles[pos] = tile;
+    }
 
-        let mut pos = rng.gen_range(0..self.tiles.len());
-        while self.tiles[pos].value() != 0 {
-            pos = rng.gen_range(0..self.tiles.len());
+    /// Generates new tile in empty space of [`Board`]
+    fn generate(&mut self) -> Spawn {
+        let mut pos = self.rng.gen_range(0..self.tiles.len());
+        while !self.tiles[pos].is_empty() {
+            pos = self.rng.gen_range(0..self.tiles.len());
         }
 
-        let rng_val = rng.gen_range(0..10);
-        if rng_val == 9 {
-            self.tiles[pos] = 4.into();
+        let tile = if self.rng.gen_bool(self.four_chance) {
+            Tile::from_exp(2)
         } else {
-            self.tiles[pos] = 2.into();
-        }
+            Tile::from_exp(1)
+        };
+        self.tiles[pos] = tile;
+        self.spawned = Some(pos);
+        Spawn { pos, tile }
+    }
+
+    /// Continues the game after the victory
+    pub fn keep_going(&mut self) {
+        self.keep_going = true;
     }
 
     /// Gets status of the game
-    fn status(&self) -> GameStatus {
-        if self.check_victory() {
+    pub fn status(&self) -> GameStatus {
+        if self.keep_going {
+            if self.check_full() {
+                GameStatus::GameOver
+            } else {
+                GameStatus::KeepGoing(self.milestone())
+            }
+        } else if self.check_victory() {
             GameStatus::Victory
         } else if self.check_full() {
             GameStatus::GameOver
@@ -161,10 +268,20 @@ impl Board {
         }
     }
 
+    /// Gets the next milestone tile after the victory
+    fn milestone(&self) -> Tile {
+        let max = self.max_tile();
+        let mut next = self.target;
+        while next <= max && next < Tile::MAX {
+            next = Tile::from_exp(next.exp() + 1);
+        }
+        next
+    }
+
     /// Checks for the victory
     fn check_victory(&self) -> bool {
         for tile in self.tiles.iter() {
-            if tile.value() == 2048 {
+            if *tile >= self.target {
                 return true;
             }
         }
@@ -176,17 +293,19 @@ impl Board {
         for y in 0..self.height {
             let offset = y * self.width;
             for x in 0..self.width {
-                if self.tiles[offset + x].value() == 0 {
+                if self.tiles[offset + x].is_empty() {
                     return false;
                 }
 
                 if let Some(tile) = self.tiles.get(offset + x + 1) {
-                    if x != self.width - 1 && *tile == self.tiles[offset + x] {
+                    if x != self.width - 1
+                        && tile.merge(&self.tiles[offset + x]).is_some()
+                    {
                         return false;
                     }
                 }
                 if let Some(tile) = self.tiles.get(offset + x + self.width) {
-                    if *tile == self.tiles[offset + x] {
+                    if tile.merge(&self.tiles[offset + x]).is_some() {
                         return false;
                     }
                 }
@@ -195,180 +314,449 @@ impl Board {
         true
     }
 
-    /// Moves tile from given position to given position
-    fn move_tile(&mut self, to: usize, from: usize) {
-        let from_val = self.tiles[from];
-        self.tiles[to] += from_val;
-        self.tiles[from] = 0.into();
+    /// Moves [`Board`] tiles in given direction, recording the slides and
+    /// merges to the outcome when given. 4x4 boards are moved using the
+    /// [`BitBoard`]. Returns whether any tile was moved
+    fn move_tiles(
+        &mut self,
+        dir: Direction,
+        outcome: Option<&mut MoveOutcome>,
+    ) -> bool {
+        match self.bitboard() {
+            Some(bits) => self.slide_bits(bits, dir, outcome),
+            None => self.slide_tiles(dir, outcome),
+        }
     }
 
-    /// Moves column up
-    fn move_up(&mut self, cur: usize) -> bool {
-        let Some(next) = self.find_up_next(cur) else {
+    /// Moves tiles of the given [`BitBoard`] of this [`Board`] in given
+    /// direction, the slides and merges are recorded using the precomputed
+    /// moves of the lines. Returns whether any tile was moved
+    fn slide_bits(
+        &mut self,
+        mut bits: BitBoard,
+        dir: Direction,
+        outcome: Option<&mut MoveOutcome>,
+    ) -> bool {
+        let lines = bits.lines(dir);
+        let before = bits;
+        let score = bits.slide(dir) as usize;
+        if bits == before {
             return false;
-        };
+        }
+        bits.write_tiles(&mut self.tiles);
+        self.score += score;
 
-        let mut change = false;
-        let cur_val = self.tiles[cur];
-        if cur_val.value() == 0 {
-            self.move_tile(cur, next);
-            self.move_up(cur);
+        let Some(outcome) = outcome else {
             return true;
+        };
+        for (line, bits) in lines.into_iter().enumerate() {
+            for (i, (to, merged)) in
+                BitBoard::line_moves(bits).into_iter().enumerate()
+            {
+                let exp = (bits >> (i * 4) & 0xf) as u8;
+                if exp == 0 || (to == i && !merged) {
+                    continue;
+                }
+                let from = self.line_index(dir, line, i);
+                let to = self.line_index(dir, line, to);
+                let tile = Tile::from_exp(exp);
+                outcome.slides.push(Slide { from, to, tile });
+                if merged {
+                    outcome.merges.push(Merge {
+                        pos: to,
+                        tile: Tile::from_exp(exp + 1),
+                    });
+                }
+            }
         }
-        if cur_val == self.tiles[next] {
-            self.move_tile(cur, next);
-            self.score += self.tiles[cur].value() as usize;
-            change = true;
-        }
-        return self.move_up(cur + self.width) || change;
+        true
     }
 
-    /// Moves column down
-    fn move_down(&mut self, cur: usize) -> bool {
-        let Some(next) = self.find_down_next(cur) else {
-            return false;
+    /// Moves [`Board`] tiles in given direction, recording the slides and
+    /// merges to the outcome when given. Returns whether any tile was moved
+    fn slide_tiles(
+        &mut self,
+        dir: Direction,
+        mut outcome: Option<&mut MoveOutcome>,
+    ) -> bool {
+        let (lines, len) = match dir {
+            Direction::Up | Direction::Down => (self.width, self.height),
+            Direction::Left | Direction::Right => (self.height, self.width),
         };
 
         let mut change = false;
-        let cur_val = self.tiles[cur];
-        if cur_val.value() == 0 {
-            self.move_tile(cur, next);
-            self.move_down(cur);
-            return true;
-        }
-        if cur_val == self.tiles[next] {
-            self.move_tile(cur, next);
-            self.score += self.tiles[cur].value() as usize;
-            change = true;
-        }
-
-        if cur < self.width {
-            return change;
+        for line in 0..lines {
+            change |= self.slide_line(dir, line, len, outcome.as_deref_mut());
         }
-        return self.move_down(cur - self.width) || change;
+        change
     }
 
-    /// Moves row right
-    fn move_right(&mut self, cur: usize, end: usize) -> bool {
-        let Some(next) = self.find_right_next(cur, end) else {
-            return false;
-        };
-
+    /// Slides single row or column of length `len` in given direction,
+    /// merging each tile at most once. Returns whether any tile was moved
+    fn slide_line(
+        &mut self,
+        dir: Direction,
+        line: usize,
+        len: usize,
+        mut outcome: Option<&mut MoveOutcome>,
+    ) -> bool {
         let mut change = false;
-        let cur_val = self.tiles[cur];
-        if cur_val.value() == 0 {
-            self.move_tile(cur, next);
-            self.move_right(cur, end);
-            return true;
+        // Position in the line, where the next tile is moved to
+        let mut next = 0;
+        // Whether the last moved tile can still be merged
+        let mut mergeable = false;
+        for i in 0..len {
+            let from = self.line_index(dir, line, i);
+            let tile = self.tiles[from];
+            if tile.is_empty() {
+                continue;
+            }
+            self.tiles[from] = Tile::EMPTY;
+
+            if mergeable {
+                let to = self.line_index(dir, line, next - 1);
+                if let Some(merged) = self.tiles[to].merge(&tile) {
+                    self.tiles[to] = merged;
+                    if let Some(outcome) = outcome.as_deref_mut() {
+                        outcome.slides.push(Slide { from, to, tile });
+                        outcome.merges.push(Merge {
+                            pos: to,
+                            tile: merged,
+                        });
+                    }
+                    self.score += merged.value() as usize;
+                    mergeable = false;
+                    change = true;
+                    continue;
+                }
+            }
+
+            let to = self.line_index(dir, line, next);
+            self.tiles[to] = tile;
+            if to != from {
+                change = true;
+                if let Some(outcome) = outcome.as_deref_mut() {
+                    outcome.slides.push(Slide { from, to, tile });
+                }
+            }
+            mergeable = true;
+            next += 1;
         }
-        if cur_val == self.tiles[next] {
-            self.move_tile(cur, next);
-            self.score += self.tiles[cur].value() as usize;
-            change = true;
+        change
+    }
+
+    /// Gets index of the `i`-th tile of the row or column, where tiles are
+    /// ordered starting from the edge the tiles are moving to
+    fn line_index(&self, dir: Direction, line: usize, i: usize) -> usize {
+        match dir {
+            Direction::Up => i * self.width + line,
+            Direction::Down => (self.height - 1 - i) * self.width + line,
+            Direction::Left => line * self.width + i,
+            Direction::Right => line * self.width + self.width - 1 - i,
         }
+    }
+}
 
-        if cur < end {
-            return change;
+impl Default for Board {
+    fn default() -> Self {
+        let seed = thread_rng().gen();
+        Self {
+            tiles: vec![Tile::EMPTY; 16],
+            score: 0,
+            moves: 0,
+            width: 4,
+            height: 4,
+            target: default_target(),
+            keep_going: false,
+            four_chance: FOUR_CHANCE,
+            spawned: None,
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
         }
-        return self.move_right(cur - 1, end) || change;
     }
+}
 
-    /// Moves row left
-    fn move_left(&mut self, cur: usize, end: usize) -> bool {
-        let Some(next) = self.find_left_next(cur, end) else {
-            return false;
-        };
+/// Gets default tile needed for victory, used by serde
+fn default_target() -> Tile {
+    DEFAULT_TARGET
+}
 
-        let mut change = false;
-        let cur_val = self.tiles[cur];
-        if cur_val.value() == 0 {
-            self.move_tile(cur, next);
-            self.move_left(cur, end);
-            return true;
-        }
-        if cur_val == self.tiles[next] {
-            self.move_tile(cur, next);
-            self.score += self.tiles[cur].value() as usize;
-            change = true;
-        }
+/// Gets default chance of generating 4, used by serde
+fn default_four_chance() -> f64 {
+    FOUR_CHANCE
+}
 
-        if cur > end {
-            return change;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::MAX_EXP;
+
+    /// Creates [`Board`] with the given tile values row by row, the rest
+    /// of the tiles is empty
+    fn board_with(width: usize, height: usize, values: &[u64]) -> Board {
+        let mut board = Board::with_seed(width, height, 0);
+        board.tiles.fill(Tile::EMPTY);
+        for (pos, value) in values.iter().enumerate() {
+            board.set_tile(pos, Tile::try_from(*value).expect("valid tile"));
         }
-        return self.move_left(cur + 1, end) || change;
+        board
     }
 
-    /// Finds next non-zero value up in column
-    fn find_up_next(&self, mut cur: usize) -> Option<usize> {
-        cur += self.width;
-        while let Some(tile) = self.tiles.get(cur) {
-            if tile.value() != 0 {
-                return Some(cur);
-            }
-            cur += self.width;
-        }
-        None
+    /// Gets values of the [`Board`] tiles row by row
+    fn values(board: &Board) -> Vec<u64> {
+        board.tiles.iter().map(|tile| tile.value()).collect()
     }
 
-    /// Finds next non-zero value down in column
-    fn find_down_next(&self, mut cur: usize) -> Option<usize> {
-        while cur >= self.width {
-            cur -= self.width;
-            if self.tiles[cur].value() != 0 {
-                return Some(cur);
-            }
+    /// Creates 4x4 board with random tiles, low tiles are used for half of
+    /// the boards, so there are many merges
+    fn random_board(rng: &mut ChaCha8Rng) -> Board {
+        let mut board = Board::with_seed(4, 4, rng.gen());
+        let max = if rng.gen_bool(0.5) { 4 } else { MAX_EXP - 1 };
+        for pos in 0..16 {
+            let exp = match rng.gen_bool(0.3) {
+                true => 0,
+                false => rng.gen_range(1..=max),
+            };
+            board.set_tile(pos, Tile::from_exp(exp));
         }
-        None
+        board
     }
 
-    /// Finds next non-zero value left in row
-    fn find_left_next(&self, mut cur: usize, end: usize) -> Option<usize> {
-        while cur < end {
-            cur += 1;
-            if self.tiles[cur].value() != 0 {
-                return Some(cur);
-            }
+    #[test]
+    fn slides_line_in_each_direction() {
+        #[rustfmt::skip]
+        let row = [
+            2, 0, 2, 4,
+            0, 0, 0, 0,
+            0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        #[rustfmt::skip]
+        let col = [
+            2, 0, 0, 0,
+            0, 0, 0, 0,
+            2, 0, 0, 0,
+            4, 0, 0, 0,
+        ];
+        #[rustfmt::skip]
+        let cases = [
+            (row, Direction::Left, [
+                4, 4, 0, 0,
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+            ]),
+            (row, Direction::Right, [
+                0, 0, 4, 4,
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+            ]),
+            (col, Direction::Up, [
+                4, 0, 0, 0,
+                4, 0, 0, 0,
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+            ]),
+            (col, Direction::Down, [
+                0, 0, 0, 0,
+                0, 0, 0, 0,
+                4, 0, 0, 0,
+                4, 0, 0, 0,
+            ]),
+        ];
+
+        for (before, dir, after) in cases {
+            let mut board = board_with(4, 4, &before);
+            assert!(board.slide_tiles(dir, None), "{dir:?}");
+            assert_eq!(values(&board), after, "{dir:?}");
         }
-        None
     }
 
-    /// Finds next non-zero value right in row
-    fn find_right_next(&self, mut cur: usize, end: usize) -> Option<usize> {
-        while cur > end {
-            cur -= 1;
-            if self.tiles[cur].value() != 0 {
-                return Some(cur);
-            }
+    #[test]
+    fn merges_each_tile_once() {
+        let cases: [(&[u64], &[u64]); 5] = [
+            (&[2, 2, 2, 2], &[4, 4, 0, 0]),
+            (&[2, 2, 4, 0], &[4, 4, 0, 0]),
+            (&[4, 2, 2, 0], &[4, 4, 0, 0]),
+            (&[2, 2, 2, 0], &[4, 2, 0, 0]),
+            (&[8, 8, 8, 8, 8], &[16, 16, 8, 0, 0]),
+        ];
+        for (before, after) in cases {
+            let mut values_before = before.to_vec();
+            values_before.extend(vec![0; before.len()]);
+            let mut board = board_with(before.len(), 2, &values_before);
+            board.slide_tiles(Direction::Left, None);
+            assert_eq!(&values(&board)[..before.len()], after);
         }
-        None
     }
 
-    /// Gets right border
-    fn get_right_border(&self) -> Layout {
-        let mut border = Layout::vertical();
-        border.add_child(
-            RawSpan::new(" ").bg(Bg::Hex(0x797979)),
-            Constrain::Length(1),
+    #[test]
+    fn keeps_board_without_possible_move() {
+        let mut board = board_with(2, 2, &[2, 4, 0, 0]);
+        assert!(!board.slide_tiles(Direction::Left, None));
+        assert!(!board.slide_tiles(Direction::Up, None));
+        assert_eq!(values(&board), [2, 4, 0, 0]);
+        assert_eq!(board.score, 0);
+    }
+
+    #[test]
+    fn reports_slides_merges_and_score() {
+        // 3x3 board uses the generic moves, 4x4 board the bitboard
+        #[rustfmt::skip]
+        let mut board = board_with(3, 3, &[
+            2, 2, 4,
+            0, 0, 2,
+            0, 0, 0,
+        ]);
+        let outcome = board.shift(Direction::Left);
+        assert!(outcome.changed);
+        assert_eq!(
+            outcome.slides,
+            [
+                Slide {
+                    from: 1,
+                    to: 0,
+                    tile: Tile::from_exp(1)
+                },
+                Slide {
+                    from: 2,
+                    to: 1,
+                    tile: Tile::from_exp(2)
+                },
+                Slide {
+                    from: 5,
+                    to: 3,
+                    tile: Tile::from_exp(1)
+                },
+            ]
+        );
+        assert_eq!(
+            outcome.merges,
+            [Merge {
+                pos: 0,
+                tile: Tile::from_exp(2)
+            }]
         );
-        border.add_child(
-            RawSpan::new(" ").bg(Bg::Hex(0x797979)),
-            Constrain::Length(1),
+        assert_eq!(outcome.score, 4);
+        assert_eq!(board.moves(), 1);
+
+        let mut board = board_with(4, 4, &[2, 2, 2, 2]);
+        let outcome = board.shift(Direction::Left);
+        assert_eq!(
+            outcome.slides,
+            [
+                Slide {
+                    from: 1,
+                    to: 0,
+                    tile: Tile::from_exp(1)
+                },
+                Slide {
+                    from: 2,
+                    to: 1,
+                    tile: Tile::from_exp(1)
+                },
+                Slide {
+                    from: 3,
+                    to: 1,
+                    tile: Tile::from_exp(1)
+                },
+            ]
         );
-        border.add_child(
-            RawSpan::new(" ").bg(Bg::Hex(0x797979)),
-            Constrain::Length(1),
+        assert_eq!(
+            outcome.merges,
+            [
+                Merge {
+                    pos: 0,
+                    tile: Tile::from_exp(2)
+                },
+                Merge {
+                    pos: 1,
+                    tile: Tile::from_exp(2)
+                },
+            ]
         );
-        border
+        assert_eq!(outcome.score, 8);
+        assert_eq!(board.score, 8);
     }
-}
 
-impl Default for Board {
-    fn default() -> Self {
-        Self {
-            tiles: vec![Tile::new(0); 16],
-            score: 0,
-            width: 4,
-            height: 4,
+    #[test]
+    fn reports_spawned_tile() {
+        let mut board = board_with(4, 4, &[0, 0, 0, 2]);
+        let outcome = board.shift(Direction::Left);
+        let spawn = outcome.spawn.expect("tile spawned after move");
+        assert_ne!(spawn.pos, 0);
+        assert!(
+            spawn.tile == Tile::from_exp(1) || spawn.tile == Tile::from_exp(2)
+        );
+        assert_eq!(board.tiles[spawn.pos], spawn.tile);
+        assert_eq!(outcome.status, GameStatus::Playing);
+    }
+
+    #[test]
+    fn reports_nothing_without_change() {
+        let mut board = board_with(4, 4, &[2, 4, 0, 0]);
+        let outcome = board.shift(Direction::Left);
+        assert!(!outcome.changed);
+        assert!(outcome.slides.is_empty());
+        assert!(outcome.merges.is_empty());
+        assert_eq!(outcome.score, 0);
+        assert_eq!(outcome.spawn, None);
+        assert_eq!(board.moves(), 0);
+    }
+
+    #[test]
+    fn same_seed_and_moves_give_same_game() {
+        let moves = [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+            Direction::Left,
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+        ];
+        let play = || {
+            let mut board = Board::with_seed(4, 4, 2048);
+            for dir in moves.iter().cycle().take(200) {
+                board.shift(*dir);
+            }
+            board
+        };
+
+        let (first, second) = (play(), play());
+        assert_eq!(first.tiles, second.tiles);
+        assert_eq!(first.score, second.score);
+        assert!(first.score > 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "board size has to be between 2 and 64")]
+    fn rejects_invalid_size() {
+        Board::with_seed(0, 4, 1);
+    }
+
+    #[test]
+    fn bitboard_matches_generic() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let board = random_board(&mut rng);
+            let bits = board.bitboard().expect("4x4 board with low tiles");
+            for dir in Direction::ALL {
+                let mut fast = board.clone();
+                let mut fast_outcome = MoveOutcome::new(GameStatus::Playing);
+                let mut generic = board.clone();
+                let mut generic_outcome = MoveOutcome::new(GameStatus::Playing);
+
+                assert_eq!(
+                    fast.slide_bits(bits, dir, Some(&mut fast_outcome)),
+                    generic.slide_tiles(dir, Some(&mut generic_outcome)),
+                );
+                assert_eq!(fast.tiles, generic.tiles);
+                assert_eq!(fast.score, generic.score);
+                assert_eq!(fast_outcome, generic_outcome);
+            }
         }
     }
 }
@@ -0,0 +1,119 @@
+use termint::{
+    enums::{cursor::Cursor, modifier::Modifier},
+    geometry::coords::Coords,
+    widgets::widget::Widget,
+};
+
+use crate::color_mode::Paint;
+
+/// Style of the single [`Canvas`] cell
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    fg: Paint,
+    bg: Paint,
+    modifier: Option<Modifier>,
+}
+
+/// Widget with fixed size, where each character can be drawn separately.
+/// It allows drawing things on any position, even over each other
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<(char, Style)>,
+}
+
+impl Style {
+    /// Creates new [`Style`] without any colors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets foreground color of [`Style`]
+    pub fn fg(mut self, fg: Paint) -> Self {
+        self.fg = fg;
+        self
+    }
+
+    /// Sets background color of [`Style`]
+    pub fn bg(mut self, bg: Paint) -> Self {
+        self.bg = bg;
+        self
+    }
+
+    /// Sets [`Style`] modifier
+    pub fn modifier<T: Into<Option<Modifier>>>(mut self, modifier: T) -> Self {
+        self.modifier = modifier.into();
+        self
+    }
+}
+
+impl Canvas {
+    /// Creates new empty [`Canvas`] with given size
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(' ', Style::new()); width * height],
+        }
+    }
+
+    /// Prints text on given position, text outside of the [`Canvas`] is
+    /// cut off
+    pub fn print(&mut self, x: usize, y: usize, text: &str, style: Style) {
+        if y >= self.height {
+            return;
+        }
+        let start = y * self.width;
+        for (x, c) in (x..self.width).zip(text.chars()) {
+            self.cells[start + x] = (c, style);
+        }
+    }
+}
+
+impl Widget for Canvas {
+    fn render(&self, pos: &Coords, size: &Coords) {
+        print!("{}", self.get_string(pos, size));
+    }
+
+    fn get_string(&self, pos: &Coords, _size: &Coords) -> String {
+        let mut res = String::new();
+        for (y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            res.push_str(&Cursor::Pos(pos.x, pos.y + y).to_string());
+            let mut last = None;
+            for (c, style) in row {
+                if last != Some(style) {
+                    res.push_str(&format!(
+                        "\x1b[0m{}{}{}",
+                        match style.bg {
+                            Paint::Default => String::new(),
+                            bg => bg.bg_code(),
+                        },
+                        style
+                            .modifier
+                            .map(|v| v.to_string())
+                            .unwrap_or_default(),
+                        style.fg.fg_code(),
+                    ));
+                    last = Some(style);
+                }
+                res.push(*c);
+            }
+            res.push_str("\x1b[0m");
+        }
+        res
+    }
+
+    fn height(&self, _size: &Coords) -> usize {
+        self.height
+    }
+
+    fn width(&self, _size: &Coords) -> usize {
+        self.width
+    }
+}
+
+impl From<Canvas> for Box<dyn Widget> {
+    fn from(value: Canvas) -> Self {
+        Box::new(value)
+    }
+}
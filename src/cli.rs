@@ -0,0 +1,57 @@
+//! Parsing of the command line values shared by the binaries, it isn't
+//! part of the engine library
+
+use std::str::FromStr;
+
+use tui2048::{
+    ai::MAX_DEPTH,
+    board::{Board, MAX_SIZE},
+    tile::Tile,
+};
+
+/// Parses value of the given argument
+pub fn parse_value<T>(arg: &str, value: Option<String>) -> Result<T, String>
+where
+    T: FromStr,
+{
+    let Some(value) = value else {
+        return Err(format!("'{arg}' expects value"));
+    };
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}' for '{arg}'"))
+}
+
+/// Parses board dimension, which has to be between 2 and [`MAX_SIZE`]
+pub fn parse_size(arg: &str, value: Option<String>) -> Result<usize, String> {
+    let size = parse_value(arg, value)?;
+    if !Board::is_valid_size(size) {
+        return Err(format!(
+            "'{arg}' has to be between 2 and {MAX_SIZE}, got {size}"
+        ));
+    }
+    Ok(size)
+}
+
+/// Parses victory tile, which has to be power of two greater than 2
+pub fn parse_target(arg: &str, value: Option<String>) -> Result<Tile, String> {
+    let target: u64 = parse_value(arg, value)?;
+    match Tile::try_from(target) {
+        Ok(tile) if tile.is_valid_target() => Ok(tile),
+        _ => Err(format!(
+            "'{arg}' has to be power of two greater than 2, got {target}"
+        )),
+    }
+}
+
+/// Parses depth of the AI search, which has to be between 1 and
+/// [`MAX_DEPTH`]
+pub fn parse_depth(arg: &str, value: Option<String>) -> Result<usize, String> {
+    let depth = parse_value(arg, value)?;
+    if !(1..=MAX_DEPTH).contains(&depth) {
+        return Err(format!(
+            "'{arg}' has to be between 1 and {MAX_DEPTH}, got {depth}"
+        ));
+    }
+    Ok(depth)
+}
@@ -0,0 +1,213 @@
+use std::{env, fmt::Display, str::FromStr};
+
+use termint::enums::fg::Fg;
+
+use crate::theme::Color;
+
+/// Colors supported by the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit colors
+    TrueColor,
+    /// 256 indexed colors
+    Ansi256,
+    /// 16 basic colors
+    Ansi16,
+    /// No colors at all
+    Never,
+}
+
+/// Color as it's printed to the terminal with the given [`ColorMode`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Paint {
+    #[default]
+    Default,
+    /// One of the 16 basic colors
+    Ansi(u8),
+    /// One of the 256 indexed colors
+    Indexed(u8),
+    Rgb(u32),
+}
+
+/// The 16 basic colors as they're usually shown by the terminals
+const ANSI16: [u32; 16] = [
+    0x000000, 0x800000, 0x008000, 0x808000, 0x000080, 0x800080, 0x008080,
+    0xc0c0c0, 0x808080, 0xff0000, 0x00ff00, 0xffff00, 0x0000ff, 0xff00ff,
+    0x00ffff, 0xffffff,
+];
+
+/// Channel values of the 6x6x6 color cube of the 256 colors
+const CUBE: [u32; 6] = [0x00, 0x5f, 0x87, 0xaf, 0xd7, 0xff];
+
+impl ColorMode {
+    /// All the color modes
+    pub const ALL: [ColorMode; 4] = [
+        ColorMode::TrueColor,
+        ColorMode::Ansi256,
+        ColorMode::Ansi16,
+        ColorMode::Never,
+    ];
+
+    /// Detects colors supported by the terminal based on the `NO_COLOR`,
+    /// `COLORTERM` and `TERM` environment variables
+    pub fn detect() -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorMode::Never;
+        }
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorMode::TrueColor;
+        }
+        match env::var("TERM") {
+            Ok(term) if term == "dumb" => ColorMode::Never,
+            Ok(term) if term.contains("direct") => ColorMode::TrueColor,
+            Ok(term) if term.contains("256") => ColorMode::Ansi256,
+            _ => ColorMode::Ansi16,
+        }
+    }
+
+    /// Gets name of the [`ColorMode`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorMode::TrueColor => "truecolor",
+            ColorMode::Ansi256 => "256",
+            ColorMode::Ansi16 => "16",
+            ColorMode::Never => "never",
+        }
+    }
+
+    /// Checks whether any colors are used
+    pub fn is_colored(&self) -> bool {
+        *self != ColorMode::Never
+    }
+
+    /// Maps the color to the nearest one supported in this mode
+    pub fn paint(&self, color: Color) -> Paint {
+        let Color::Rgb(rgb) = color else {
+            return Paint::Default;
+        };
+        match self {
+            ColorMode::TrueColor => Paint::Rgb(rgb),
+            ColorMode::Ansi256 => Paint::Indexed(Self::nearest_256(rgb)),
+            ColorMode::Ansi16 => Paint::Ansi(Self::nearest_16(rgb)),
+            ColorMode::Never => Paint::Default,
+        }
+    }
+
+    /// Gets the color as termint foreground color. Termint doesn't support
+    /// the 256 colors, so the 16 colors are used instead
+    pub fn fg(&self, color: Color) -> Fg {
+        match (self, color) {
+            (ColorMode::Never, _) | (_, Color::Default) => Fg::Default,
+            (ColorMode::TrueColor, Color::Rgb(rgb)) => Fg::Hex(rgb),
+            (_, Color::Rgb(rgb)) => match Self::nearest_16(rgb) {
+                0 => Fg::Black,
+                1 => Fg::DarkRed,
+                2 => Fg::DarkGreen,
+                3 => Fg::DarkYellow,
+                4 => Fg::DarkBlue,
+                5 => Fg::DarkMagenta,
+                6 => Fg::DarkCyan,
+                7 => Fg::LightGray,
+                8 => Fg::Gray,
+                9 => Fg::Red,
+                10 => Fg::Green,
+                11 => Fg::Yellow,
+                12 => Fg::Blue,
+                13 => Fg::Magenta,
+                14 => Fg::Cyan,
+                _ => Fg::White,
+            },
+        }
+    }
+
+    /// Gets index of the nearest of the 16 basic colors
+    fn nearest_16(rgb: u32) -> u8 {
+        (0..16)
+            .min_by_key(|&i| Self::distance(rgb, ANSI16[i]))
+            .unwrap_or_default() as u8
+    }
+
+    /// Gets index of the nearest of the 256 colors, only the color cube and
+    /// the grayscale are used, because the first 16 colors differ between
+    /// the terminals
+    fn nearest_256(rgb: u32) -> u8 {
+        let channel = |shift: u32| {
+            let value = rgb >> shift & 0xff;
+            (0..6)
+                .min_by_key(|&i| CUBE[i].abs_diff(value))
+                .unwrap_or_default()
+        };
+        let (r, g, b) = (channel(16), channel(8), channel(0));
+        let cube = (CUBE[r] << 16) | (CUBE[g] << 8) | CUBE[b];
+
+        let avg = ((rgb >> 16 & 0xff) + (rgb >> 8 & 0xff) + (rgb & 0xff)) / 3;
+        let gray_index = (avg.saturating_sub(3) / 10).min(23);
+        let level = 8 + gray_index * 10;
+        let gray = (level << 16) | (level << 8) | level;
+
+        if Self::distance(rgb, gray) < Self::distance(rgb, cube) {
+            232 + gray_index as u8
+        } else {
+            16 + (r * 36 + g * 6 + b) as u8
+        }
+    }
+
+    /// Gets squared distance of two colors weighted by the eye sensitivity
+    fn distance(a: u32, b: u32) -> u32 {
+        let diff = |shift: u32| (a >> shift & 0xff).abs_diff(b >> shift & 0xff);
+        let (r, g, b) = (diff(16), diff(8), diff(0));
+        2 * r * r + 4 * g * g + 3 * b * b
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s)
+            .ok_or_else(|| format!("unknown color mode '{s}'"))
+    }
+}
+
+impl Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Paint {
+    /// Gets ANSI code setting the [`Paint`] as foreground color
+    pub fn fg_code(&self) -> String {
+        match self {
+            Paint::Default => "\x1b[39m".to_string(),
+            Paint::Ansi(i) if *i < 8 => format!("\x1b[{}m", 30 + i),
+            Paint::Ansi(i) => format!("\x1b[{}m", 82 + i),
+            Paint::Indexed(i) => format!("\x1b[38;5;{i}m"),
+            Paint::Rgb(rgb) => format!(
+                "\x1b[38;2;{};{};{}m",
+                rgb >> 16 & 0xff,
+                rgb >> 8 & 0xff,
+                rgb & 0xff
+            ),
+        }
+    }
+
+    /// Gets ANSI code setting the [`Paint`] as background color
+    pub fn bg_code(&self) -> String {
+        match self {
+            Paint::Default => "\x1b[49m".to_string(),
+            Paint::Ansi(i) if *i < 8 => format!("\x1b[{}m", 40 + i),
+            Paint::Ansi(i) => format!("\x1b[{}m", 92 + i),
+            Paint::Indexed(i) => format!("\x1b[48;5;{i}m"),
+            Paint::Rgb(rgb) => format!(
+                "\x1b[48;2;{};{};{}m",
+                rgb >> 16 & 0xff,
+                rgb >> 8 & 0xff,
+                rgb & 0xff
+            ),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use tui2048::{
+    ai::MAX_DEPTH,
+    board::{Board, FOUR_CHANCE, MAX_SIZE},
+    tile::Tile,
+};
+
+use crate::{
+    args::Args,
+    color_mode::ColorMode,
+    error::Error,
+    keys::{Action, Keys},
+};
+
+/// Settings loaded from the config file, missing settings keep their
+/// default values
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub target: Option<u64>,
+    /// Name of built-in or user theme, or path to a theme file
+    pub theme: Option<String>,
+    pub color: Option<String>,
+    pub ascii: Option<bool>,
+    /// Duration of move animation in milliseconds
+    pub animation: Option<u64>,
+    pub undo_depth: Option<usize>,
+    pub ai_depth: Option<usize>,
+    /// Delay between autoplay moves in milliseconds
+    pub ai_delay: Option<u64>,
+    /// Time limit for searching hint in milliseconds
+    pub hint_time: Option<u64>,
+    /// Chance that the generated tile is 4 instead of 2
+    pub four_chance: Option<f64>,
+    /// Asks for confirmation before quitting the game
+    pub confirm_quit: bool,
+    /// Asks for confirmation before restarting the game
+    pub confirm_restart: bool,
+    /// Keys bound to the actions by the action names
+    pub keys: BTreeMap<String, Vec<String>>,
+    /// Path to the loaded file, used in error messages
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Config {
+    /// Loads [`Config`] from the config file in the config directory,
+    /// missing file gives the default config
+    pub fn load_default() -> Result<Self, Error> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Loads [`Config`] from the given TOML file and checks its settings
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::InvalidConfig(format!("{}: {e}", path.display()))
+        })?;
+        let mut config: Config = toml::from_str(&content).map_err(|e| {
+            let line = e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1);
+            let location = match line {
+                Some(line) => format!("{}:{line}", path.display()),
+                None => path.display().to_string(),
+            };
+            Error::InvalidConfig(format!("{location}: {}", e.message()))
+        })?;
+        config.path = path.to_path_buf();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Gets path to the config file in the config directory
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("tui2048").join("config.toml"))
+    }
+
+    /// Applies the config to the given [`Args`], which are then updated by
+    /// the command line arguments
+    pub fn apply(&self, mut args: Args) -> Result<Args, Error> {
+        if let Some(width) = self.width {
+            args.width = self.size("width", width)?;
+        }
+        if let Some(height) = self.height {
+            args.height = self.size("height", height)?;
+        }
+        if let Some(target) = self.target {
+            args.target = match Tile::try_from(target) {
+                Ok(tile) if tile.is_valid_target() => tile,
+                _ => {
+                    return Err(self.error(format!(
+                        "'target' has to be power of two greater than 2, \
+                        got {target}"
+                    )))
+                }
+            };
+        }
+        if let Some(color) = &self.color {
+            args.color = Some(color.parse().map_err(|_| {
+                self.error(format!(
+                    "invalid value '{color}' for 'color', expected one of {}",
+                    ColorMode::ALL.map(|m| format!("'{m}'")).join(", ")
+                ))
+            })?);
+        }
+        if self.theme.is_some() {
+            args.theme.clone_from(&self.theme);
+        }
+        if let Some(ascii) = self.ascii {
+            args.ascii = ascii;
+        }
+        if let Some(animation) = self.animation {
+            args.anim_time = Duration::from_millis(animation);
+        }
+        if let Some(depth) = self.undo_depth {
+            args.undo_depth = depth;
+        }
+        if let Some(depth) = self.ai_depth {
+            if !(1..=MAX_DEPTH).contains(&depth) {
+                return Err(self.error(format!(
+                    "'ai_depth' has to be between 1 and {MAX_DEPTH}, \
+                    got {depth}"
+                )));
+            }
+            args.ai_depth = depth;
+        }
+        if let Some(delay) = self.ai_delay {
+            args.ai_delay = Duration::from_millis(delay);
+        }
+        if let Some(time) = self.hint_time {
+            args.hint_time = Duration::from_millis(time);
+        }
+        Ok(args)
+    }
+
+    /// Gets chance that the generated tile is 4 instead of 2
+    pub fn four_chance(&self) -> f64 {
+        self.four_chance.unwrap_or(FOUR_CHANCE)
+    }
+
+    /// Gets the key bindings with the configured keys
+    pub fn keys(&self) -> Result<Keys, Error> {
+        let mut bindings = vec![];
+        for (action, keys) in &self.keys {
+            let action: Action = action.parse().map_err(|e| {
+                let actions = Action::ALL.map(|a| format!("'{a}'")).join(", ");
+                self.error(format!("{e} in 'keys', expected one of {actions}"))
+            })?;
+            let keys = keys
+                .iter()
+                .map(|key| Keys::parse_key(key))
+                .collect::<Result<_, _>>()
+                .map_err(|e| self.error(format!("{e} for '{action}'")))?;
+            bindings.push((action, keys));
+        }
+        Keys::with_bindings(&bindings).map_err(|e| self.error(e))
+    }
+
+    /// Checks that all the settings are valid
+    fn validate(&self) -> Result<(), Error> {
+        self.apply(Args::default())?;
+        if let Some(chance) = self.four_chance {
+            if !(0. ..=1.).contains(&chance) {
+                return Err(self.error(format!(
+                    "'four_chance' has to be between 0 and 1, got {chance}"
+                )));
+            }
+        }
+        self.keys()?;
+        Ok(())
+    }
+
+    /// Creates error of invalid config with the config path
+    fn error(&self, msg: String) -> Error {
+        Error::InvalidConfig(format!("{}: {msg}", self.path.display()))
+    }
+
+    /// Checks board dimension, which has to be between 2 and [`MAX_SIZE`]
+    fn size(&self, name: &str, size: usize) -> Result<usize, Error> {
+        if !Board::is_valid_size(size) {
+            return Err(self.error(format!(
+                "'{name}' has to be between 2 and {MAX_SIZE}, got {size}"
+            )));
+        }
+        Ok(size)
+    }
+}
@@ -0,0 +1,43 @@
+/// Direction in which the tiles can be moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All the directions
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// Gets arrow pointing in the [`Direction`]
+    pub fn arrow(&self) -> char {
+        match self {
+            Direction::Up => '↑',
+            Direction::Down => '↓',
+            Direction::Left => '←',
+            Direction::Right => '→',
+        }
+    }
+
+    /// Gets letter representing the [`Direction`], used in replays
+    pub fn letter(&self) -> char {
+        match self {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        }
+    }
+
+    /// Gets [`Direction`] from the letter given by [`Direction::letter`]
+    pub fn from_letter(letter: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|dir| dir.letter() == letter)
+    }
+}
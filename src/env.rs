@@ -0,0 +1,130 @@
+use crate::{
+    board::{Board, DEFAULT_TARGET},
+    direction::Direction,
+    game_status::GameStatus,
+    tile::Tile,
+};
+
+/// Gym-style environment for training agents. The game continues after
+/// reaching the target tile, until no move is possible
+pub struct Env {
+    board: Board,
+    width: usize,
+    height: usize,
+    target: Tile,
+}
+
+/// Observation of the [`Env`] state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    /// Tiles row by row as exponents of two, zero is empty tile
+    pub grid: Vec<u8>,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Additional information about the [`Env`] state after the step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Info {
+    /// Whether the action moved any tile, illegal actions do nothing
+    pub changed: bool,
+    pub score: usize,
+    pub moves: usize,
+    pub max_tile: Tile,
+    /// Whether the target tile was reached
+    pub won: bool,
+    /// Legal actions in the order of [`Direction::ALL`]
+    pub legal: [bool; 4],
+}
+
+impl Env {
+    /// Creates new [`Env`] with board of the given size, the game starts
+    /// with random seed
+    ///
+    /// # Panics
+    ///
+    /// Panics when width or height isn't between 2 and
+    /// [`MAX_SIZE`](crate::board::MAX_SIZE)
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut board = Board::new(width, height);
+        board.keep_going();
+        Self {
+            board,
+            width,
+            height,
+            target: DEFAULT_TARGET,
+        }
+    }
+
+    /// Sets value of the tile reported as won in the [`Info`]
+    ///
+    /// # Panics
+    ///
+    /// Panics when the target isn't greater than 2
+    pub fn with_target(mut self, target: Tile) -> Self {
+        self.target = target;
+        self.board = self.board.with_target(target);
+        self
+    }
+
+    /// Starts new game with the given seed
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.board = Board::with_seed(self.width, self.height, seed)
+            .with_target(self.target);
+        self.board.keep_going();
+        self.observation()
+    }
+
+    /// Makes the action. Returns the new observation, reward (score gained
+    /// by the move), whether the game is over and additional information
+    pub fn step(
+        &mut self,
+        action: Direction,
+    ) -> (Observation, f64, bool, Info) {
+        let outcome = self.board.shift(action);
+        let done = outcome.status == GameStatus::GameOver;
+        let mut info = self.info();
+        info.changed = outcome.changed;
+        (self.observation(), outcome.score as f64, done, info)
+    }
+
+    /// Gets legal actions in the order of [`Direction::ALL`], action is
+    /// legal when it moves any tile
+    pub fn legal_actions(&self) -> [bool; 4] {
+        Direction::ALL.map(|dir| self.board.clone().slide(dir))
+    }
+
+    /// Gets current observation
+    pub fn observation(&self) -> Observation {
+        Observation {
+            grid: self.board.tiles().iter().map(|t| t.exp()).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Gets the underlying [`Board`]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Gets information about the current state
+    fn info(&self) -> Info {
+        let max_tile = self.board.max_tile();
+        Info {
+            changed: false,
+            score: self.board.score,
+            moves: self.board.moves(),
+            max_tile,
+            won: max_tile >= self.target,
+            legal: self.legal_actions(),
+        }
+    }
+}
+
+impl Observation {
+    /// Gets exponent of the tile on the given position, zero when empty
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        self.grid[y * self.width + x]
+    }
+}
@@ -1,5 +1,14 @@
+use std::fmt::Display;
+
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     IOError(std::io::Error),
+    Args(String),
+    CorruptSave(String),
+    CorruptScores(String),
+    CorruptReplay(String),
+    InvalidTheme(String),
+    InvalidConfig(String),
     Exit,
 }
 
@@ -8,3 +17,18 @@ impl From<std::io::Error> for Error {
         Self::IOError(value)
     }
 }
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IOError(e) => write!(f, "{e}"),
+            Error::Args(e) => write!(f, "{e}"),
+            Error::CorruptSave(e) => write!(f, "corrupted save: {e}"),
+            Error::CorruptScores(e) => write!(f, "corrupted scores: {e}"),
+            Error::CorruptReplay(e) => write!(f, "corrupted replay: {e}"),
+            Error::InvalidTheme(e) => write!(f, "invalid theme: {e}"),
+            Error::InvalidConfig(e) => write!(f, "invalid config {e}"),
+            Error::Exit => write!(f, "exit"),
+        }
+    }
+}
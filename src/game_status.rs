@@ -1,10 +1,17 @@
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
+use crate::tile::Tile;
+
 /// Represents status the game is currently in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameStatus {
     Playing,
     GameOver,
     Victory,
+    /// Playing after the victory, contains the next milestone tile
+    KeepGoing(Tile),
 }
 
 impl Display for GameStatus {
@@ -13,6 +20,7 @@ impl Display for GameStatus {
             GameStatus::Playing => write!(f, ""),
             GameStatus::GameOver => write!(f, "Game Over!"),
             GameStatus::Victory => write!(f, "Victory!"),
+            GameStatus::KeepGoing(next) => write!(f, "Next: {next}"),
         }
     }
 }
@@ -0,0 +1,70 @@
+use std::{
+    io::{stdout, Write},
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Once,
+    },
+};
+
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use signal_hook::{consts::signal, flag};
+
+use crate::error::Error;
+
+/// Prepares terminal for the game and restores it when dropped, on panic
+/// and on termination signals, so the terminal is never left in raw mode
+pub struct TermGuard {
+    /// Set when termination signal was received
+    exit: Arc<AtomicBool>,
+}
+
+impl TermGuard {
+    /// Saves screen, clears it, hides cursor and enables raw mode
+    pub fn new() -> Result<Self, Error> {
+        Self::set_panic_hook();
+        let exit = Arc::new(AtomicBool::new(false));
+        flag::register(signal::SIGTERM, exit.clone())?;
+        flag::register(signal::SIGINT, exit.clone())?;
+        #[cfg(unix)]
+        flag::register(signal::SIGHUP, exit.clone())?;
+
+        // Restores the terminal when enabling raw mode fails
+        let guard = Self { exit };
+        print!("\x1b[?1049h\x1b[2J\x1b[?25l");
+        _ = stdout().flush();
+        enable_raw_mode()?;
+        Ok(guard)
+    }
+
+    /// Checks whether termination signal was received
+    pub fn should_exit(&self) -> bool {
+        self.exit.load(Ordering::Relaxed)
+    }
+
+    /// Disables raw mode, restores screen and shows cursor
+    fn restore() {
+        _ = disable_raw_mode();
+        print!("\x1b[?1049l\x1b[?25h");
+        _ = stdout().flush();
+    }
+
+    /// Restores the terminal before printing the panic message, otherwise
+    /// it would be printed to the alternate screen and lost
+    fn set_panic_hook() {
+        static HOOK: Once = Once::new();
+        HOOK.call_once(|| {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info| {
+                Self::restore();
+                hook(info);
+            }));
+        });
+    }
+}
+
+impl Drop for TermGuard {
+    fn drop(&mut self) {
+        Self::restore();
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use crate::board::Snapshot;
+
+/// Default number of moves that can be undone
+pub const DEFAULT_DEPTH: usize = 32;
+
+/// Undo and redo history of the [`Board`](crate::board::Board) moves
+pub struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    depth: usize,
+    undos: usize,
+}
+
+impl History {
+    /// Creates new [`History`] storing at most `depth` moves
+    pub fn new(depth: usize) -> Self {
+        Self {
+            undo: VecDeque::with_capacity(depth),
+            redo: vec![],
+            depth,
+            undos: 0,
+        }
+    }
+
+    /// Sets maximum number of moves stored in the [`History`]
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        self.undo.truncate(depth);
+    }
+
+    /// Pushes state before the move to the [`History`] and clears redo
+    pub fn push(&mut self, snapshot: Snapshot) {
+        self.redo.clear();
+        self.push_undo(snapshot);
+    }
+
+    /// Undoes the last move, `current` is the state before undoing.
+    /// Returns state the board should be restored to
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo.pop_front()?;
+        self.redo.push(current);
+        self.undos += 1;
+        Some(snapshot)
+    }
+
+    /// Redoes the last undone move, `current` is the state before redoing.
+    /// Returns state the board should be restored to
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.redo.pop()?;
+        self.push_undo(current);
+        Some(snapshot)
+    }
+
+    /// Clears the [`History`] and resets the undo counter
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.undos = 0;
+    }
+
+    /// Gets how many times undo was used
+    pub fn undos(&self) -> usize {
+        self.undos
+    }
+
+    /// Pushes snapshot to the undo stack, dropping the oldest one when full
+    fn push_undo(&mut self, snapshot: Snapshot) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_back();
+        }
+        self.undo.push_front(snapshot);
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEPTH)
+    }
+}
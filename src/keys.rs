@@ -0,0 +1,258 @@
+use std::{fmt::Display, str::FromStr};
+
+use crossterm::event::KeyCode;
+
+/// Action on the game screen, which can be bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Undo,
+    Redo,
+    KeepGoing,
+    Hint,
+    Autoplay,
+    Slower,
+    Faster,
+    Restart,
+    Scores,
+    Replay,
+    SaveReplay,
+    Theme,
+    ZoomIn,
+    ZoomOut,
+    ZoomAuto,
+    Help,
+    Quit,
+}
+
+/// Key bindings of the game screen actions
+#[derive(Debug, Clone)]
+pub struct Keys {
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+impl Action {
+    /// All the actions
+    pub const ALL: [Action; 21] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Undo,
+        Action::Redo,
+        Action::KeepGoing,
+        Action::Hint,
+        Action::Autoplay,
+        Action::Slower,
+        Action::Faster,
+        Action::Restart,
+        Action::Scores,
+        Action::Replay,
+        Action::SaveReplay,
+        Action::Theme,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::ZoomAuto,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    /// Gets name of the [`Action`] used in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::KeepGoing => "keep_going",
+            Action::Hint => "hint",
+            Action::Autoplay => "autoplay",
+            Action::Slower => "slower",
+            Action::Faster => "faster",
+            Action::Restart => "restart",
+            Action::Scores => "scores",
+            Action::Replay => "replay",
+            Action::SaveReplay => "save_replay",
+            Action::Theme => "theme",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::ZoomAuto => "zoom_auto",
+            Action::Help => "help",
+            Action::Quit => "quit",
+        }
+    }
+
+    /// Gets keys bound to the [`Action`] by default
+    fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            Action::Up => vec![KeyCode::Up],
+            Action::Down => vec![KeyCode::Down],
+            Action::Left => vec![KeyCode::Left],
+            Action::Right => vec![KeyCode::Right],
+            Action::Undo => vec![KeyCode::Char('u')],
+            Action::Redo => vec![KeyCode::Char('y')],
+            Action::KeepGoing => vec![KeyCode::Char('k')],
+            Action::Hint => vec![KeyCode::Char('h')],
+            Action::Autoplay => vec![KeyCode::Char('a')],
+            Action::Slower => vec![KeyCode::Char('[')],
+            Action::Faster => vec![KeyCode::Char(']')],
+            Action::Restart => vec![KeyCode::Char('r')],
+            Action::Scores => vec![KeyCode::Char('s')],
+            Action::Replay => vec![KeyCode::Char('p')],
+            Action::SaveReplay => vec![KeyCode::Char('w')],
+            Action::Theme => vec![KeyCode::Char('t')],
+            Action::ZoomIn => vec![KeyCode::Char('+'), KeyCode::Char('=')],
+            Action::ZoomOut => vec![KeyCode::Char('-')],
+            Action::ZoomAuto => vec![KeyCode::Char('0')],
+            Action::Help => vec![KeyCode::Char('i')],
+            Action::Quit => vec![KeyCode::Esc, KeyCode::Char('q')],
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.name() == s)
+            .ok_or_else(|| format!("unknown action '{s}'"))
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Keys {
+    /// Gets [`Action`] bound to the given key
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(key, _)| *key == code)
+            .map(|(_, action)| *action)
+    }
+
+    /// Gets keys bound to the given [`Action`]
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, a)| *a == action)
+            .map(|(key, _)| *key)
+    }
+
+    /// Creates [`Keys`] with the default bindings, where the given actions
+    /// are bound to the given keys instead. The given keys are removed from
+    /// the default bindings of other actions. Fails when any key is given
+    /// for more actions
+    pub fn with_bindings(
+        bindings: &[(Action, Vec<KeyCode>)],
+    ) -> Result<Self, String> {
+        let mut keys = Self::default();
+        keys.bindings.retain(|(key, action)| {
+            bindings
+                .iter()
+                .all(|(a, codes)| a != action && !codes.contains(key))
+        });
+        for (action, codes) in bindings {
+            for key in codes {
+                match keys.action(*key) {
+                    Some(other) if other == *action => continue,
+                    Some(other) => {
+                        return Err(format!(
+                        "key '{}' is bound to both '{other}' and '{action}'",
+                        Self::key_name(*key, true)
+                    ))
+                    }
+                    None => keys.bindings.push((*key, *action)),
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Gets names of the keys bound to the [`Action`] separated by `/`
+    pub fn describe(&self, action: Action, ascii: bool) -> String {
+        self.keys(action)
+            .map(|key| Self::key_name(key, ascii))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Parses key name, which is either single character or name of the
+    /// special key (`Up`, `Esc`, `Space`, ...)
+    pub fn parse_key(name: &str) -> Result<KeyCode, String> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(KeyCode::Char(c));
+        }
+        match name.to_lowercase().as_str() {
+            "up" => Ok(KeyCode::Up),
+            "down" => Ok(KeyCode::Down),
+            "left" => Ok(KeyCode::Left),
+            "right" => Ok(KeyCode::Right),
+            "esc" => Ok(KeyCode::Esc),
+            "enter" => Ok(KeyCode::Enter),
+            "space" => Ok(KeyCode::Char(' ')),
+            "tab" => Ok(KeyCode::Tab),
+            "backspace" => Ok(KeyCode::Backspace),
+            "delete" => Ok(KeyCode::Delete),
+            "home" => Ok(KeyCode::Home),
+            "end" => Ok(KeyCode::End),
+            "pageup" => Ok(KeyCode::PageUp),
+            "pagedown" => Ok(KeyCode::PageDown),
+            _ => Err(format!("unknown key '{name}'")),
+        }
+    }
+
+    /// Gets name of the key as shown in the help, arrows are shown as
+    /// arrow symbols unless only ASCII is used
+    pub fn key_name(key: KeyCode, ascii: bool) -> String {
+        let name = match (key, ascii) {
+            (KeyCode::Char(' '), _) => "Space",
+            (KeyCode::Char(c), _) => return c.to_string(),
+            (KeyCode::Up, false) => "↑",
+            (KeyCode::Down, false) => "↓",
+            (KeyCode::Left, false) => "←",
+            (KeyCode::Right, false) => "→",
+            (KeyCode::Up, true) => "Up",
+            (KeyCode::Down, true) => "Down",
+            (KeyCode::Left, true) => "Left",
+            (KeyCode::Right, true) => "Right",
+            (KeyCode::Esc, _) => "Esc",
+            (KeyCode::Enter, _) => "Enter",
+            (KeyCode::Tab, _) => "Tab",
+            (KeyCode::Backspace, _) => "Backspace",
+            (KeyCode::Delete, _) => "Delete",
+            (KeyCode::Home, _) => "Home",
+            (KeyCode::End, _) => "End",
+            (KeyCode::PageUp, _) => "PageUp",
+            (KeyCode::PageDown, _) => "PageDown",
+            _ => "?",
+        };
+        name.to_string()
+    }
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .flat_map(|action| {
+                action
+                    .default_keys()
+                    .into_iter()
+                    .map(move |key| (key, action))
+            })
+            .collect();
+        Self { bindings }
+    }
+}
@@ -0,0 +1,15 @@
+//! 2048 game engine without any UI. It contains the board with its moves,
+//! undo history, replays and AI, which can be used by the bots and tools,
+//! while the `tui2048` binary only renders it in the terminal.
+
+pub mod ai;
+pub mod bitboard;
+pub mod board;
+pub mod direction;
+pub mod env;
+pub mod game_status;
+pub mod history;
+pub mod outcome;
+pub mod replay;
+pub mod strategy;
+pub mod tile;
@@ -1,13 +1,129 @@
+use std::{env, path::Path, process::ExitCode};
+
 use app::App;
+use args::Args;
+use color_mode::ColorMode;
+use config::Config;
+use error::Error;
+use player::Player;
+use save::Save;
+use theme::Theme;
+use tui2048::board::Board;
 
+mod animation;
 mod app;
-mod board;
+mod args;
+mod atomic;
+mod canvas;
+mod cli;
+mod color_mode;
+mod config;
 mod error;
-mod game_status;
+mod guard;
+mod keys;
+mod player;
 mod raw_span;
-mod tile;
+mod renderer;
+mod replays;
+mod save;
+mod scores;
+mod theme;
+
+fn main() -> ExitCode {
+    let argv: Vec<String> = env::args().skip(1).collect();
+    let args = match Args::parse(argv.clone()) {
+        Ok(args) => args,
+        Err(Error::Args(e)) => {
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+        Err(_) => return ExitCode::FAILURE,
+    };
+
+    if args.help {
+        Args::help();
+        return ExitCode::SUCCESS;
+    }
+    if args.version {
+        println!("tui2048 {}", env!("CARGO_PKG_VERSION"));
+        return ExitCode::SUCCESS;
+    }
+
+    // Command line arguments override the config
+    let config = match &args.config {
+        Some(path) => Config::load(path),
+        None => Config::load_default(),
+    };
+    let (config, args, keys) = match config.and_then(|config| {
+        let args = config.apply(Args::default())?.update(argv)?;
+        let keys = config.keys()?;
+        Ok((config, args, keys))
+    }) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut themes, theme_error) = Theme::load_all();
+    let theme = match &args.theme {
+        Some(name) => match themes.iter().position(|t| &t.name == name) {
+            Some(index) => index,
+            None => match Theme::load(Path::new(name)) {
+                Ok(theme) => {
+                    themes.push(theme);
+                    themes.len() - 1
+                }
+                Err(e) => {
+                    eprintln!("Error: cannot load theme '{name}': {e}");
+                    return ExitCode::FAILURE;
+                }
+            },
+        },
+        None => 0,
+    };
 
-fn main() {
-    let mut app = App::new(4, 4);
-    _ = app.run();
+    let board = match args.seed {
+        Some(seed) => Board::with_seed(args.width, args.height, seed),
+        None => Board::new(args.width, args.height),
+    }
+    .with_target(args.target)
+    .with_four_chance(config.four_chance());
+    let app = App::new(board);
+    let app = match (&args.replay, &args.load, args.seed) {
+        (Some(path), _, _) => match replays::load(path).and_then(Player::new) {
+            Ok(player) => app.with_player(player),
+            Err(e) => {
+                eprintln!("Error: cannot load '{}': {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, Some(path), _) => match Save::load_from(path) {
+            Ok(save) => app.with_save(save).keep_save(),
+            Err(e) => {
+                eprintln!("Error: cannot load '{}': {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        (None, None, Some(_)) => app.keep_save(),
+        (None, None, None) => app.resume(),
+    };
+    let mut app = app
+        .color_mode(args.color.unwrap_or_else(ColorMode::detect))
+        .ascii(args.ascii)
+        .themes(themes, theme)
+        .message(theme_error)
+        .keys(keys)
+        .confirm(config.confirm_quit, config.confirm_restart)
+        .undo_depth(args.undo_depth)
+        .ai_depth(args.ai_depth)
+        .ai_delay(args.ai_delay)
+        .hint_time(args.hint_time)
+        .anim_time(args.anim_time);
+    if let Err(Error::IOError(e)) = app.run() {
+        eprintln!("Error: {e}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
 }
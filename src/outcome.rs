@@ -0,0 +1,57 @@
+use crate::{game_status::GameStatus, tile::Tile};
+
+/// Outcome of a single move on the [`Board`](crate::board::Board)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// Whether any tile moved, new tile is generated only when it did
+    pub changed: bool,
+    /// Tiles that moved to other position, including the merged ones
+    pub slides: Vec<Slide>,
+    /// Tiles created by merging
+    pub merges: Vec<Merge>,
+    /// Score gained by the move
+    pub score: usize,
+    /// Tile generated after the move
+    pub spawn: Option<Spawn>,
+    /// Status of the game after the move
+    pub status: GameStatus,
+}
+
+/// Tile moved from one position to another, positions are indexes to
+/// [`Board::tiles`](crate::board::Board::tiles)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slide {
+    pub from: usize,
+    pub to: usize,
+    /// Value of the tile before merging
+    pub tile: Tile,
+}
+
+/// Two tiles merged into one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Merge {
+    pub pos: usize,
+    /// Value of the resulting tile
+    pub tile: Tile,
+}
+
+/// Newly generated tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spawn {
+    pub pos: usize,
+    pub tile: Tile,
+}
+
+impl MoveOutcome {
+    /// Creates new empty [`MoveOutcome`] with given status
+    pub(crate) fn new(status: GameStatus) -> Self {
+        Self {
+            changed: false,
+            slides: vec![],
+            merges: vec![],
+            score: 0,
+            spawn: None,
+            status,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+
+use tui2048::{
+    board::{Board, Snapshot},
+    outcome::MoveOutcome,
+    replay::Replay,
+};
+
+use crate::error::Error;
+
+/// Shortest delay between the played moves at normal speed
+const MIN_GAP: Duration = Duration::from_millis(50);
+/// Longest delay between the played moves at normal speed, so the long
+/// pauses in the game are skipped
+const MAX_GAP: Duration = Duration::from_secs(1);
+const MIN_SPEED: f64 = 0.125;
+const MAX_SPEED: f64 = 16.;
+
+/// Plays back the [`Replay`]
+pub struct Player {
+    replay: Replay,
+    board: Board,
+    /// Snapshots before each of the played moves, used for stepping back
+    history: Vec<Snapshot>,
+    playing: bool,
+    speed: f64,
+    next: Instant,
+}
+
+impl Player {
+    /// Creates new [`Player`] at the start of the given [`Replay`]. Fails
+    /// when any of the recorded moves can't be made
+    pub fn new(replay: Replay) -> Result<Self, Error> {
+        let mut board = replay.board();
+        for i in 0..replay.len() {
+            let (dir, _) = replay.get(i).ok_or_else(|| {
+                Error::CorruptReplay(format!("invalid move {}", i + 1))
+            })?;
+            if !board.shift(dir).changed {
+                return Err(Error::CorruptReplay(format!(
+                    "move {} doesn't move any tile",
+                    i + 1
+                )));
+            }
+        }
+
+        Ok(Self {
+            board: replay.board(),
+            replay,
+            history: vec![],
+            playing: true,
+            speed: 1.,
+            next: Instant::now(),
+        })
+    }
+
+    /// Gets the [`Board`] after the played moves
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Gets number of the played moves
+    pub fn pos(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Gets number of all the moves in the [`Replay`]
+    pub fn len(&self) -> usize {
+        self.replay.len()
+    }
+
+    /// Checks whether the [`Replay`] is being played
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Gets playback speed multiplier
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Gets time when the next move should be played, [`None`] when paused
+    pub fn next(&self) -> Option<Instant> {
+        self.playing.then_some(self.next)
+    }
+
+    /// Plays the next move, pauses the playback at the end of the replay
+    pub fn step(&mut self) -> Option<MoveOutcome> {
+        let Some((dir, _)) = self.replay.get(self.pos()) else {
+            self.playing = false;
+            return None;
+        };
+        self.history.push(self.board.snapshot());
+        let outcome = self.board.shift(dir);
+        self.next = Instant::now() + self.delay();
+        Some(outcome)
+    }
+
+    /// Returns to the state before the last played move
+    pub fn back(&mut self) {
+        if let Some(snapshot) = self.history.pop() {
+            self.board.restore(snapshot);
+        }
+    }
+
+    /// Toggles between playing and pause, playing at the end of the replay
+    /// starts it from the beginning
+    pub fn toggle(&mut self) {
+        self.playing = !self.playing;
+        if self.playing && self.pos() == self.len() {
+            self.board = self.replay.board();
+            self.history.clear();
+        }
+        self.next = Instant::now();
+    }
+
+    /// Doubles the playback speed
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2.).min(MAX_SPEED);
+    }
+
+    /// Halves the playback speed
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2.).max(MIN_SPEED);
+    }
+
+    /// Gets delay before the next move, based on the time between the moves
+    /// in the recorded game
+    pub fn delay(&self) -> Duration {
+        let pos = self.pos();
+        let time = |i| self.replay.get(i).map(|(_, time)| time);
+        let gap = match (pos.checked_sub(1).and_then(time), time(pos)) {
+            (Some(last), Some(next)) => next.saturating_sub(last),
+            _ => MIN_GAP,
+        };
+        gap.clamp(MIN_GAP, MAX_GAP).div_f64(self.speed)
+    }
+}
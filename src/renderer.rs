@@ -0,0 +1,563 @@
+use termint::enums::{fg::Fg, modifier::Modifier};
+use tui2048::{board::Board, tile::Tile};
+
+use crate::{
+    animation::Animation,
+    canvas::{Canvas, Style},
+    color_mode::{ColorMode, Paint},
+    theme::{Color, Theme, TileColor},
+};
+
+/// Renders the game engine types as termint widgets
+pub struct Renderer {
+    mode: ColorMode,
+    theme: Theme,
+    /// Draws only ASCII characters when true
+    ascii: bool,
+    size: TileSize,
+}
+
+/// Size of the rendered tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileSize {
+    /// Single line tiles
+    Compact,
+    Normal,
+    /// Tiles with the value written using big digits
+    Large,
+}
+
+impl Renderer {
+    /// Creates new [`Renderer`] using the given [`ColorMode`]
+    pub fn new(mode: ColorMode) -> Self {
+        Self {
+            mode,
+            theme: Theme::default(),
+            ascii: false,
+            size: TileSize::Normal,
+        }
+    }
+
+    /// Gets the [`TileSize`] used for rendering
+    pub fn tile_size(&self) -> TileSize {
+        self.size
+    }
+
+    /// Sets the [`TileSize`] used for rendering
+    pub fn set_tile_size(&mut self, size: TileSize) {
+        self.size = size;
+    }
+
+    /// Checks whether only ASCII characters are drawn
+    pub fn is_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    /// Sets whether only ASCII characters are drawn
+    pub fn set_ascii(&mut self, ascii: bool) {
+        self.ascii = ascii;
+    }
+
+    /// Sets [`ColorMode`] of the terminal the [`Renderer`] draws to
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.mode = mode;
+    }
+
+    /// Sets the [`Theme`] used for rendering
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Gets the accent color of the [`Theme`]
+    pub fn accent(&self) -> Fg {
+        self.fg(self.theme.accent)
+    }
+
+    /// Gets the highlight color of the [`Theme`]
+    pub fn highlight(&self) -> Fg {
+        self.fg(self.theme.highlight)
+    }
+
+    /// Gets the hint color of the [`Theme`]
+    pub fn hint(&self) -> Fg {
+        self.fg(self.theme.hint)
+    }
+
+    /// Gets the error color of the [`Theme`]
+    pub fn error(&self) -> Fg {
+        self.fg(self.theme.error)
+    }
+
+    /// Gets [`Board`] as termint widget
+    pub fn board(&self, board: &Board) -> Canvas {
+        let mut canvas = self.grid(board);
+        for (pos, tile) in board.tiles().iter().enumerate() {
+            self.draw_tile(&mut canvas, self.tile_pos(board, pos), tile);
+        }
+        canvas
+    }
+
+    /// Gets current frame of the move [`Animation`] as termint widget
+    pub fn animation(&self, board: &Board, anim: &Animation) -> Canvas {
+        let mut canvas = self.grid(board);
+        let outcome = anim.outcome();
+        let Some(slide) = anim.slide() else {
+            let appear = anim.appear();
+            for (pos, tile) in board.tiles().iter().enumerate() {
+                let (x, y) = self.tile_pos(board, pos);
+                match outcome.spawn {
+                    Some(spawn) if spawn.pos == pos => {
+                        self.draw_spawn(&mut canvas, (x, y), tile, appear)
+                    }
+                    _ => self.draw_tile(&mut canvas, (x, y), tile),
+                }
+            }
+            if appear < 1.0 {
+                for merge in outcome.merges.iter() {
+                    let (x, y) = self.tile_pos(board, merge.pos);
+                    self.draw_pop(&mut canvas, (x, y), &merge.tile);
+                }
+            }
+            return canvas;
+        };
+
+        for (pos, tile) in anim.tiles().iter().enumerate() {
+            if outcome.slides.iter().all(|s| s.from != pos) {
+                self.draw_tile(&mut canvas, self.tile_pos(board, pos), tile);
+            }
+        }
+        for s in outcome.slides.iter() {
+            let (fx, fy) = self.tile_pos(board, s.from);
+            let (tx, ty) = self.tile_pos(board, s.to);
+            let x = fx as f64 + (tx as f64 - fx as f64) * slide;
+            let y = fy as f64 + (ty as f64 - fy as f64) * slide;
+            let pos = (x.round() as usize, y.round() as usize);
+            self.draw_tile(&mut canvas, pos, &s.tile);
+        }
+        canvas
+    }
+
+    /// Gets width of the rendered [`Board`]
+    pub fn board_width(&self, board: &Board) -> usize {
+        self.board_size(board, self.size).0
+    }
+
+    /// Gets height of the rendered [`Board`]
+    pub fn board_height(&self, board: &Board) -> usize {
+        self.board_size(board, self.size).1
+    }
+
+    /// Gets the largest [`TileSize`], with which the [`Board`] fits into the
+    /// given size. Returns the smallest size when none of them fits
+    pub fn fit_tile_size(
+        &self,
+        board: &Board,
+        width: usize,
+        height: usize,
+    ) -> TileSize {
+        TileSize::ALL
+            .into_iter()
+            .rev()
+            .find(|size| {
+                let (w, h) = self.board_size(board, *size);
+                w <= width && h <= height
+            })
+            .unwrap_or(TileSize::Compact)
+    }
+
+    /// Gets size of the rendered [`Board`] with the given [`TileSize`]
+    fn board_size(&self, board: &Board, size: TileSize) -> (usize, usize) {
+        let (width, height) = board.size();
+        let (cell_w, cell_h) = self.cell_size(size);
+        let borders = match (self.ascii, size) {
+            (true, TileSize::Compact) | (false, _) => 2,
+            (true, _) => 1,
+        };
+        (width * cell_w + 1, height * cell_h + borders)
+    }
+
+    /// Gets size of the tile including the grid on its left and the top
+    /// and bottom bars of the tile (or the grid line above it in ASCII)
+    fn cell_size(&self, size: TileSize) -> (usize, usize) {
+        let height = match (self.ascii, size) {
+            (_, TileSize::Compact) => 1,
+            (true, size) => size.inner_height() + 1,
+            (false, size) => size.inner_height() + 2,
+        };
+        (size.inner_width() + 1, height)
+    }
+
+    /// Gets [`Canvas`] with the board borders and empty tiles
+    fn grid(&self, board: &Board) -> Canvas {
+        let width = self.board_width(board);
+        let height = self.board_height(board);
+        let mut canvas = Canvas::new(width, height);
+
+        let border = Style::new().fg(self.paint(self.theme.grid));
+        if self.ascii {
+            let inner = self.size.inner_width();
+            let cell_h = self.cell_size(self.size).1;
+            let line = format!("+{}", "-".repeat(inner)).repeat(board.size().0);
+            let row = format!("|{}", " ".repeat(inner)).repeat(board.size().0);
+            for y in 0..height {
+                let is_line = match self.size {
+                    TileSize::Compact => y == 0 || y == height - 1,
+                    _ => y % cell_h == 0,
+                };
+                let (text, end) =
+                    if is_line { (&line, "+") } else { (&row, "|") };
+                canvas.print(0, y, &format!("{text}{end}"), border);
+            }
+        } else {
+            canvas.print(0, 0, &"▂".repeat(width), border);
+            canvas.print(0, height - 1, &"▔".repeat(width), border);
+
+            let right = Style::new().bg(self.paint(self.theme.grid));
+            for y in 1..height - 1 {
+                canvas.print(width - 1, y, " ", right);
+            }
+        }
+        for pos in 0..board.tiles().len() {
+            let pos = self.tile_pos(board, pos);
+            self.draw_tile(&mut canvas, pos, &Tile::EMPTY);
+        }
+        canvas
+    }
+
+    /// Gets position of the top left corner of the tile on the [`Canvas`]
+    fn tile_pos(&self, board: &Board, pos: usize) -> (usize, usize) {
+        let width = board.size().0;
+        let (cell_w, cell_h) = self.cell_size(self.size);
+        let top = if self.ascii { 0 } else { 1 };
+        (pos % width * cell_w, pos / width * cell_h + top)
+    }
+
+    /// Gets position of the first row inside the tile, which has its top
+    /// left corner on the given row
+    fn inner_top(&self, y: usize) -> usize {
+        match (self.ascii, self.size) {
+            (_, TileSize::Compact) => y,
+            _ => y + 1,
+        }
+    }
+
+    /// Draws tile on the given position
+    fn draw_tile(&self, canvas: &mut Canvas, pos: (usize, usize), tile: &Tile) {
+        if self.ascii {
+            let style = self.ascii_style(self.theme.tile(tile));
+            self.draw_ascii(canvas, pos, tile, style);
+        } else if self.mode.is_colored() {
+            self.draw_colored(canvas, pos, tile, self.theme.tile(tile));
+        } else {
+            self.draw_plain(canvas, pos, tile);
+        }
+    }
+
+    /// Draws tile with the given color
+    fn draw_colored(
+        &self,
+        canvas: &mut Canvas,
+        (x, y): (usize, usize),
+        tile: &Tile,
+        color: TileColor,
+    ) {
+        let grid = Style::new().bg(self.paint(self.theme.grid));
+        let inner = Style::new().bg(self.paint(color.bg));
+        let top = self.inner_top(y);
+        let lines = self.value_lines(tile, false);
+        let bottom = top + lines.len();
+        for (i, line) in lines.iter().enumerate() {
+            canvas.print(x, top + i, " ", grid);
+            canvas.print(x + 1, top + i, line, inner.fg(self.paint(color.fg)));
+        }
+        if self.size == TileSize::Compact {
+            return;
+        }
+
+        let width = self.size.inner_width();
+        canvas.print(x, y, " ", grid);
+        let top_bar = grid.fg(self.paint(color.bg));
+        canvas.print(x + 1, y, &"▆".repeat(width), top_bar);
+        canvas.print(x, bottom, " ", grid);
+        let bottom_bar = inner.fg(self.paint(self.theme.grid));
+        canvas.print(x + 1, bottom, &"▂".repeat(width), bottom_bar);
+    }
+
+    /// Draws value of the tile inside the ASCII grid
+    fn draw_ascii(
+        &self,
+        canvas: &mut Canvas,
+        (x, y): (usize, usize),
+        tile: &Tile,
+        style: Style,
+    ) {
+        let dot = !self.mode.is_colored();
+        for (i, line) in self.value_lines(tile, dot).iter().enumerate() {
+            canvas.print(x + 1, y + 1 + i, line, style);
+        }
+    }
+
+    /// Gets style of the tile value inside the ASCII grid
+    fn ascii_style(&self, color: TileColor) -> Style {
+        Style::new()
+            .bg(self.paint(color.bg))
+            .fg(self.paint(color.fg))
+    }
+
+    /// Draws tile without any colors
+    fn draw_plain(
+        &self,
+        canvas: &mut Canvas,
+        (x, y): (usize, usize),
+        tile: &Tile,
+    ) {
+        let top = self.inner_top(y);
+        let lines = self.value_lines(tile, true);
+        let bottom = top + lines.len();
+        let style = match tile.is_empty() {
+            true => Style::new(),
+            false => Style::new().modifier(Modifier::Inverse),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            canvas.print(x, top + i, " ", Style::new());
+            canvas.print(x + 1, top + i, line, style);
+        }
+        if self.size == TileSize::Compact {
+            return;
+        }
+
+        let width = self.size.inner_width();
+        let (top_bar, bottom_bar) = match tile.is_empty() {
+            true => (" ".repeat(width), " ".repeat(width)),
+            false => ("▆".repeat(width), "▂".repeat(width)),
+        };
+        canvas.print(x, y, &format!(" {top_bar}"), Style::new());
+        canvas.print(x, bottom, " ", Style::new());
+        canvas.print(x + 1, bottom, &bottom_bar, style);
+    }
+
+    /// Draws merged tile enlarged over the surrounding grid
+    fn draw_pop(
+        &self,
+        canvas: &mut Canvas,
+        (x, y): (usize, usize),
+        tile: &Tile,
+    ) {
+        if self.ascii {
+            let style = self.ascii_style(self.theme.tile(tile));
+            self.draw_ascii(
+                canvas,
+                (x, y),
+                tile,
+                style.modifier(Modifier::Bold),
+            );
+            return;
+        }
+
+        let width = self.size.inner_width() + 2;
+        let top = self.inner_top(y);
+        let lines = self.value_lines(tile, false);
+        let bottom = top + lines.len();
+        let (bar, inner, value) = if self.mode.is_colored() {
+            let color = self.theme.tile(tile);
+            let bar = Style::new().fg(self.paint(color.bg));
+            let inner = Style::new().bg(self.paint(color.bg));
+            let value = inner.fg(self.paint(color.fg)).modifier(Modifier::Bold);
+            (bar, inner, value)
+        } else {
+            let inverse = Style::new().modifier(Modifier::Inverse);
+            (Style::new(), inverse, inverse)
+        };
+        for (i, line) in lines.iter().enumerate() {
+            canvas.print(x, top + i, &format!(" {line} "), value);
+        }
+        if self.size == TileSize::Compact {
+            return;
+        }
+
+        let (top_bar, bottom_bar) = match self.mode.is_colored() {
+            true => ("█".repeat(width), " ".repeat(width)),
+            false => ("▆".repeat(width), "▂".repeat(width)),
+        };
+        canvas.print(x, y, &top_bar, bar);
+        canvas.print(x, bottom, &bottom_bar, inner);
+    }
+
+    /// Draws newly generated tile fading in, `appear` is the fade progress
+    /// from 0 to 1
+    fn draw_spawn(
+        &self,
+        canvas: &mut Canvas,
+        (x, y): (usize, usize),
+        tile: &Tile,
+        appear: f64,
+    ) {
+        let shown = if appear < 0.5 { &Tile::EMPTY } else { tile };
+        if self.mode.is_colored() {
+            let from = self.theme.tile(&Tile::EMPTY);
+            let to = self.theme.tile(tile);
+            let color = TileColor {
+                bg: from.bg.blend(&to.bg, appear),
+                fg: to.fg,
+            };
+            if self.ascii {
+                let style = self.ascii_style(color);
+                self.draw_ascii(canvas, (x, y), shown, style);
+            } else {
+                self.draw_colored(canvas, (x, y), shown, color);
+            }
+        } else if shown.is_empty() {
+            let dim = Style::new().modifier(Modifier::Dim);
+            let top = if self.ascii { y + 1 } else { self.inner_top(y) };
+            let lines = self.value_lines(tile, false);
+            for (i, line) in lines.iter().enumerate() {
+                canvas.print(x + 1, top + i, line, dim);
+            }
+        } else if self.ascii {
+            self.draw_ascii(canvas, (x, y), tile, Style::new());
+        } else {
+            self.draw_plain(canvas, (x, y), tile);
+        }
+    }
+
+    /// Gets the given color as termint foreground color
+    fn fg(&self, color: Color) -> Fg {
+        self.mode.fg(color)
+    }
+
+    /// Gets the given color mapped to the [`ColorMode`]
+    fn paint(&self, color: Color) -> Paint {
+        self.mode.paint(color)
+    }
+
+    /// Gets lines inside the tile with its value in the middle. Large tiles
+    /// show the value with big digits, unless only ASCII is used. `dot`
+    /// marks empty tile with dot in the middle
+    fn value_lines(&self, tile: &Tile, dot: bool) -> Vec<String> {
+        let width = self.size.inner_width();
+        let height = self.size.inner_height();
+        if self.size == TileSize::Large && !self.ascii && !tile.is_empty() {
+            return Self::big_digits(&tile.to_string())
+                .iter()
+                .map(|line| Self::center(line, width))
+                .collect();
+        }
+
+        let value = match (tile.is_empty(), dot, self.ascii) {
+            (false, _, _) => tile.to_string(),
+            (true, true, true) => ".".to_string(),
+            (true, true, false) => "·".to_string(),
+            (true, false, _) => String::new(),
+        };
+        let mut lines = vec![" ".repeat(width); height];
+        lines[height / 2] = Self::center(&value, width);
+        lines
+    }
+
+    /// Centers the text so its length is `width`, odd padding is put on the
+    /// left side
+    fn center(text: &str, width: usize) -> String {
+        let pad = width.saturating_sub(text.chars().count());
+        let pad_r = pad / 2;
+        format!("{}{}{}", " ".repeat(pad - pad_r), text, " ".repeat(pad_r))
+    }
+
+    /// Gets the text written with big 3x3 characters separated by space.
+    /// Each character is 3x5 pixels, two pixels above each other are drawn
+    /// using single half block character
+    fn big_digits(text: &str) -> [String; 3] {
+        let mut lines: [String; 3] = Default::default();
+        for (i, c) in text.chars().enumerate() {
+            let glyph = Self::glyph(c);
+            let pixel = |row: usize, col: usize| {
+                row < 5 && glyph >> (14 - row * 3 - col) & 1 == 1
+            };
+            for (y, line) in lines.iter_mut().enumerate() {
+                if i > 0 {
+                    line.push(' ');
+                }
+                for x in 0..3 {
+                    line.push(match (pixel(y * 2, x), pixel(y * 2 + 1, x)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    });
+                }
+            }
+        }
+        lines
+    }
+
+    /// Gets 3x5 pixels of the character row by row, the top left pixel is
+    /// in the highest bit
+    fn glyph(c: char) -> u16 {
+        match c {
+            '0' => 0b111_101_101_101_111,
+            '1' => 0b010_110_010_010_111,
+            '2' => 0b111_001_111_100_111,
+            '3' => 0b111_001_111_001_111,
+            '4' => 0b101_101_111_001_001,
+            '5' => 0b111_100_111_001_111,
+            '6' => 0b111_100_111_101_111,
+            '7' => 0b111_001_001_001_001,
+            '8' => 0b111_101_111_101_111,
+            '9' => 0b111_101_111_001_111,
+            'K' => 0b101_101_110_101_101,
+            'M' => 0b101_111_111_101_101,
+            'G' => 0b111_100_101_101_111,
+            'T' => 0b111_010_010_010_010,
+            'P' => 0b111_101_111_100_100,
+            'E' => 0b111_100_110_100_111,
+            _ => 0,
+        }
+    }
+}
+
+impl TileSize {
+    /// All the tile sizes from the smallest
+    pub const ALL: [TileSize; 3] =
+        [TileSize::Compact, TileSize::Normal, TileSize::Large];
+
+    /// Gets name of the [`TileSize`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            TileSize::Compact => "compact",
+            TileSize::Normal => "normal",
+            TileSize::Large => "large",
+        }
+    }
+
+    /// Gets the next larger [`TileSize`], the largest stays the same
+    pub fn larger(&self) -> Self {
+        match self {
+            TileSize::Compact => TileSize::Normal,
+            _ => TileSize::Large,
+        }
+    }
+
+    /// Gets the next smaller [`TileSize`], the smallest stays the same
+    pub fn smaller(&self) -> Self {
+        match self {
+            TileSize::Large => TileSize::Normal,
+            _ => TileSize::Compact,
+        }
+    }
+
+    /// Gets width of the tile without the grid
+    fn inner_width(&self) -> usize {
+        match self {
+            TileSize::Compact | TileSize::Normal => 5,
+            // Five big digits
+            TileSize::Large => 19,
+        }
+    }
+
+    /// Gets number of the lines inside the tile
+    fn inner_height(&self) -> usize {
+        match self {
+            TileSize::Compact | TileSize::Normal => 1,
+            TileSize::Large => 3,
+        }
+    }
+}
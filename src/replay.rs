@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::{Board, FOUR_CHANCE},
+    direction::Direction,
+    tile::Tile,
+};
+
+/// Recorded game, which can be played back. It stores only the initial
+/// board settings and the moves, since the game is deterministic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    width: usize,
+    height: usize,
+    seed: u64,
+    target: Tile,
+    #[serde(default = "default_four_chance")]
+    four_chance: f64,
+    /// Moves as letters given by [`Direction::letter`]
+    moves: String,
+    /// Time since the start of the game of each move in milliseconds
+    times: Vec<u64>,
+}
+
+impl Replay {
+    /// Creates new empty [`Replay`] of the game on the given [`Board`],
+    /// which has to be without any moves
+    pub fn new(board: &Board) -> Self {
+        let (width, height) = board.size();
+        Self {
+            width,
+            height,
+            seed: board.seed(),
+            target: board.target(),
+            four_chance: board.four_chance(),
+            moves: String::new(),
+            times: vec![],
+        }
+    }
+
+    /// Gets the [`Board`] at the start of the recorded game
+    pub fn board(&self) -> Board {
+        Board::with_seed(self.width, self.height, self.seed)
+            .with_target(self.target)
+            .with_four_chance(self.four_chance)
+    }
+
+    /// Adds move made in the given time since the start of the game
+    pub fn push(&mut self, dir: Direction, time: Duration) {
+        self.moves.push(dir.letter());
+        self.times.push(time.as_millis() as u64);
+    }
+
+    /// Removes the last move, used when the move is undone
+    pub fn pop(&mut self) -> Option<(Direction, Duration)> {
+        let dir = Direction::from_letter(self.moves.pop()?)?;
+        let time = Duration::from_millis(self.times.pop()?);
+        Some((dir, time))
+    }
+
+    /// Gets move on the given index and time when it was made
+    pub fn get(&self, index: usize) -> Option<(Direction, Duration)> {
+        let dir =
+            Direction::from_letter(*self.moves.as_bytes().get(index)? as char)?;
+        let time = Duration::from_millis(*self.times.get(index)?);
+        Some((dir, time))
+    }
+
+    /// Gets number of the recorded moves
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Checks whether there are no recorded moves
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Checks whether the [`Replay`] can be played back, which might not be
+    /// true when it was deserialized from corrupted file. It doesn't check
+    /// whether the moves are possible
+    pub fn is_valid(&self) -> bool {
+        Board::is_valid_size(self.width)
+            && Board::is_valid_size(self.height)
+            && self.target.is_valid_target()
+            && (0. ..=1.).contains(&self.four_chance)
+            && self.moves.len() == self.times.len()
+            && self
+                .moves
+                .chars()
+                .all(|c| Direction::from_letter(c).is_some())
+    }
+}
+
+/// Gets default chance of generating 4, used by serde
+fn default_four_chance() -> f64 {
+    FOUR_CHANCE
+}
@@ -0,0 +1,47 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use tui2048::replay::Replay;
+
+use crate::{atomic, error::Error};
+
+/// Loads [`Replay`] from the given file
+pub fn load(path: &Path) -> Result<Replay, Error> {
+    let content = fs::read_to_string(path)?;
+    let replay: Replay = serde_json::from_str(&content)
+        .map_err(|e| Error::CorruptReplay(e.to_string()))?;
+    if !replay.is_valid() {
+        return Err(Error::CorruptReplay("invalid replay".to_string()));
+    }
+    Ok(replay)
+}
+
+/// Saves [`Replay`] to the replays directory, file is named by the current
+/// date and time, with a counter when such file already exists. Returns
+/// path to the saved file
+pub fn store(replay: &Replay) -> Result<PathBuf, Error> {
+    let Some(dir) = dir() else {
+        return Err(Error::IOError(io::Error::new(
+            ErrorKind::NotFound,
+            "data directory not found",
+        )));
+    };
+    let name = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let mut path = dir.join(format!("{name}.json"));
+    let mut counter = 1;
+    while path.exists() {
+        counter += 1;
+        path = dir.join(format!("{name}_{counter}.json"));
+    }
+    atomic::write_json(&path, replay)?;
+    Ok(path)
+}
+
+/// Gets directory where the replays are saved
+fn dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("tui2048").join("replays"))
+}
@@ -0,0 +1,118 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use tui2048::{board::Board, game_status::GameStatus, replay::Replay};
+
+use crate::{atomic, error::Error};
+
+/// Version of the save file format, increase it on incompatible changes
+const VERSION: u32 = 1;
+
+/// Game saved on exit and offered for resume on the next start
+#[derive(Serialize, Deserialize)]
+pub struct Save {
+    version: u32,
+    pub board: Board,
+    pub status: GameStatus,
+    #[serde(default)]
+    pub played: Duration,
+    #[serde(default)]
+    pub replay: Option<Replay>,
+}
+
+/// Used for checking the save version before parsing the rest of the file
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl Save {
+    /// Creates new [`Save`] from given board, its status, how long the
+    /// game has been played and its replay
+    pub fn new(
+        board: Board,
+        status: GameStatus,
+        played: Duration,
+        replay: Option<Replay>,
+    ) -> Self {
+        Self {
+            version: VERSION,
+            board,
+            status,
+            played,
+            replay,
+        }
+    }
+
+    /// Loads [`Save`] from the save file. Returns [`None`] when there is no
+    /// saved game
+    pub fn load() -> Result<Option<Self>, Error> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        match Self::load_from(&path) {
+            Err(Error::IOError(e)) if e.kind() == ErrorKind::NotFound => {
+                Ok(None)
+            }
+            res => res.map(Some),
+        }
+    }
+
+    /// Loads [`Save`] from the given file
+    pub fn load_from(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+
+        let header: Header = serde_json::from_str(&content)
+            .map_err(|e| Error::CorruptSave(e.to_string()))?;
+        if header.version != VERSION {
+            return Err(Error::CorruptSave(format!(
+                "unsupported save version {}",
+                header.version
+            )));
+        }
+
+        let save: Self = serde_json::from_str(&content)
+            .map_err(|e| Error::CorruptSave(e.to_string()))?;
+        if !save.board.is_valid()
+            || save.replay.as_ref().is_some_and(|r| !r.is_valid())
+        {
+            return Err(Error::CorruptSave("invalid board".to_string()));
+        }
+        Ok(save)
+    }
+
+    /// Checks whether there is a saved game
+    pub fn exists() -> bool {
+        Self::path().is_some_and(|path| path.exists())
+    }
+
+    /// Saves [`Save`] to the save file
+    pub fn store(&self) -> Result<(), Error> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        atomic::write_json(&path, self)
+    }
+
+    /// Removes the save file, so the game isn't offered for resume
+    pub fn remove() -> Result<(), Error> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets path to the save file
+    fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("tui2048").join("save.json"))
+    }
+}
@@ -0,0 +1,183 @@
+use std::{fmt::Display, fs, io::ErrorKind, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use tui2048::{
+    board::{Board, DEFAULT_TARGET, FOUR_CHANCE},
+    tile::Tile,
+};
+
+use crate::{atomic, error::Error};
+
+/// Version of the scores file format, increase it on incompatible changes
+const VERSION: u32 = 1;
+/// Maximum number of records stored for each [`ScoreKey`]
+const MAX_RECORDS: usize = 10;
+
+/// Local high-score store
+#[derive(Serialize, Deserialize)]
+pub struct Scores {
+    version: u32,
+    tables: Vec<Table>,
+}
+
+/// Identifies which games can be compared with each other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreKey {
+    pub width: usize,
+    pub height: usize,
+    #[serde(default = "default_target")]
+    pub target: Tile,
+    pub variant: String,
+}
+
+/// High-scores of the games with the same [`ScoreKey`]
+#[derive(Serialize, Deserialize)]
+pub struct Table {
+    pub key: ScoreKey,
+    pub records: Vec<Record>,
+}
+
+/// Single record in the high-score table
+#[derive(Serialize, Deserialize)]
+pub struct Record {
+    pub score: usize,
+    pub max_tile: Tile,
+    pub moves: usize,
+    pub duration: Duration,
+    pub date: DateTime<Local>,
+}
+
+impl Scores {
+    /// Loads [`Scores`] from the scores file, returns empty [`Scores`] when
+    /// there is no such file
+    pub fn load() -> Result<Self, Error> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let scores: Self = serde_json::from_str(&content)
+            .map_err(|e| Error::CorruptScores(e.to_string()))?;
+        if scores.version != VERSION {
+            return Err(Error::CorruptScores(format!(
+                "unsupported scores version {}",
+                scores.version
+            )));
+        }
+        Ok(scores)
+    }
+
+    /// Saves [`Scores`] to the scores file
+    pub fn store(&self) -> Result<(), Error> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        atomic::write_json(&path, self)
+    }
+
+    /// Adds game on the given board to the [`Scores`]. Returns position of
+    /// the record in its table, or [`None`] when it isn't high enough
+    pub fn add(&mut self, board: &Board, duration: Duration) -> Option<usize> {
+        let key = ScoreKey::new(board);
+        let record = Record {
+            score: board.score,
+            max_tile: board.max_tile(),
+            moves: board.moves(),
+            duration,
+            date: Local::now(),
+        };
+
+        let id = match self.tables.iter().position(|t| t.key == key) {
+            Some(id) => id,
+            None => {
+                self.tables.push(Table {
+                    key,
+                    records: vec![],
+                });
+                self.tables.len() - 1
+            }
+        };
+
+        let records = &mut self.tables[id].records;
+        let pos = records.partition_point(|r| r.score >= record.score);
+        if pos >= MAX_RECORDS {
+            return None;
+        }
+        records.insert(pos, record);
+        records.truncate(MAX_RECORDS);
+        Some(pos)
+    }
+
+    /// Removes record on the given position in the given table
+    pub fn remove(&mut self, table: usize, pos: usize) {
+        if let Some(table) = self.tables.get_mut(table) {
+            if pos < table.records.len() {
+                table.records.remove(pos);
+            }
+        }
+    }
+
+    /// Gets all the high-score tables
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Gets path to the scores file
+    fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("tui2048").join("scores.json"))
+    }
+}
+
+impl Default for Scores {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            tables: vec![],
+        }
+    }
+}
+
+impl ScoreKey {
+    /// Creates [`ScoreKey`] of the game on the given board
+    pub fn new(board: &Board) -> Self {
+        let (width, height) = board.size();
+        Self {
+            width,
+            height,
+            target: board.target(),
+            variant: Self::variant(board.four_chance()),
+        }
+    }
+
+    /// Gets name of the game variant by the chance of spawning 4, games
+    /// with the default chance are classic
+    fn variant(four_chance: f64) -> String {
+        if four_chance == FOUR_CHANCE {
+            return "classic".to_string();
+        }
+        format!("{}% fours", (four_chance * 1000.).round() / 10.)
+    }
+}
+
+impl Display for ScoreKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{} {} to {}",
+            self.width, self.height, self.variant, self.target
+        )
+    }
+}
+
+/// Gets target of the records stored before target could be changed
+fn default_target() -> Tile {
+    DEFAULT_TARGET
+}
@@ -0,0 +1,150 @@
+use std::{str::FromStr, thread};
+
+use tui2048::{ai, board::DEFAULT_TARGET, strategy::Strategy, tile::Tile};
+
+use crate::cli;
+
+/// Format of the printed statistics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+/// Parsed command line arguments of the simulation
+#[derive(Debug)]
+pub struct Args {
+    pub games: usize,
+    pub strategy: Strategy,
+    pub width: usize,
+    pub height: usize,
+    pub target: Tile,
+    pub seed: Option<u64>,
+    pub threads: usize,
+    pub depth: usize,
+    pub format: Format,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    /// Parses arguments from the given iterator (without program name)
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut res = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => res.help = true,
+                "-v" | "--version" => res.version = true,
+                "-n" | "--games" => {
+                    res.games = cli::parse_value(&arg, args.next())?
+                }
+                "-S" | "--strategy" => {
+                    res.strategy = cli::parse_value(&arg, args.next())?
+                }
+                "--width" => {
+                    res.width = cli::parse_size(&arg, args.next())?;
+                }
+                "--height" => {
+                    res.height = cli::parse_size(&arg, args.next())?;
+                }
+                "-t" | "--target" => {
+                    res.target = cli::parse_target(&arg, args.next())?;
+                }
+                "-s" | "--seed" => {
+                    res.seed = Some(cli::parse_value(&arg, args.next())?)
+                }
+                "-j" | "--threads" => {
+                    res.threads =
+                        cli::parse_value::<usize>(&arg, args.next())?.max(1)
+                }
+                "--depth" => res.depth = cli::parse_depth(&arg, args.next())?,
+                "-f" | "--format" => {
+                    res.format = cli::parse_value(&arg, args.next())?
+                }
+                _ => return Err(format!("invalid argument '{arg}'")),
+            }
+        }
+        Ok(res)
+    }
+
+    /// Prints help
+    pub fn help() {
+        println!("Runs 2048 games without UI and prints their statistics\n");
+        println!("Usage:");
+        println!("  tui2048-sim [flags]\n");
+        println!("Flags:");
+        Self::help_flag("-h  --help", "Prints this help");
+        Self::help_flag("-v  --version", "Prints version");
+        Self::help_flag("-n  --games <count>", "Sets number of games [100]");
+        Self::help_flag(
+            "-S  --strategy <strategy>",
+            "Sets strategy: random, corner or expectimax [corner]",
+        );
+        Self::help_flag("    --width <columns>", "Sets board width [4]");
+        Self::help_flag("    --height <rows>", "Sets board height [4]");
+        Self::help_flag(
+            "-t  --target <tile>",
+            "Sets tile needed for victory [2048]",
+        );
+        Self::help_flag(
+            "-s  --seed <seed>",
+            "Sets seed of the first game, the next games use following seeds",
+        );
+        Self::help_flag(
+            "-j  --threads <count>",
+            "Sets number of threads [available cores]",
+        );
+        Self::help_flag(
+            "    --depth <moves>",
+            &format!(
+                "Sets how many moves expectimax looks ahead [{}]",
+                ai::DEFAULT_DEPTH
+            ),
+        );
+        Self::help_flag(
+            "-f  --format <format>",
+            "Sets output format: text, csv or json [text]",
+        );
+    }
+
+    /// Prints single flag with its description
+    fn help_flag(flag: &str, description: &str) {
+        println!("  {flag:<27}{description}");
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{s}'")),
+        }
+    }
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            strategy: Strategy::Corner,
+            width: 4,
+            height: 4,
+            target: DEFAULT_TARGET,
+            seed: None,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            depth: ai::DEFAULT_DEPTH,
+            format: Format::Text,
+            help: false,
+            version: false,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    env,
+    process::ExitCode,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Instant,
+};
+
+use args::{Args, Format};
+use rand::{thread_rng, Rng};
+use stats::{GameResult, Stats};
+use tui2048::{board::Board, game_status::GameStatus, strategy::Bot};
+
+mod args;
+#[path = "../cli.rs"]
+mod cli;
+mod stats;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.help {
+        Args::help();
+        return ExitCode::SUCCESS;
+    }
+    if args.version {
+        println!("tui2048-sim {}", env!("CARGO_PKG_VERSION"));
+        return ExitCode::SUCCESS;
+    }
+
+    let start = Instant::now();
+    let Some(results) = run(&args) else {
+        eprintln!("Error: simulation failed");
+        return ExitCode::FAILURE;
+    };
+    let stats = Stats::new(&args, &results, start.elapsed());
+    match args.format {
+        Format::Text => print!("{}", stats.text()),
+        Format::Csv => print!("{}", stats.csv()),
+        Format::Json => match serde_json::to_string_pretty(&stats) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+    ExitCode::SUCCESS
+}
+
+/// Runs all the games on multiple threads. Returns [`None`] when any of the
+/// threads panicked
+fn run(args: &Args) -> Option<Vec<GameResult>> {
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let next = AtomicUsize::new(0);
+    thread::scope(|s| {
+        let workers: Vec<_> = (0..args.threads.min(args.games))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let game = next.fetch_add(1, Ordering::Relaxed);
+                        if game >= args.games {
+                            return results;
+                        }
+                        results
+                            .push(play(args, seed.wrapping_add(game as u64)));
+                    }
+                })
+            })
+            .collect();
+        // All the workers are joined, so the panic isn't raised again
+        let results: Vec<_> = workers
+            .into_iter()
+            .map(|worker| worker.join().ok())
+            .collect();
+        results
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .map(|results| results.into_iter().flatten().collect())
+    })
+}
+
+/// Plays single game until it's over, the game continues after reaching
+/// the target tile
+fn play(args: &Args, seed: u64) -> GameResult {
+    let mut board = Board::with_seed(args.width, args.height, seed)
+        .with_target(args.target);
+    board.keep_going();
+    let mut bot = Bot::new(args.strategy, seed).depth(args.depth);
+    while let Some(dir) = bot.next_move(&board) {
+        if board.shift(dir).status == GameStatus::GameOver {
+            break;
+        }
+    }
+    GameResult::new(&board)
+}
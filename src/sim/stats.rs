@@ -0,0 +1,203 @@
+use std::{fmt::Write, time::Duration};
+
+use serde::Serialize;
+use tui2048::{board::Board, tile::Tile};
+
+use crate::args::Args;
+
+/// Result of a single simulated game
+pub struct GameResult {
+    pub score: usize,
+    pub max_tile: Tile,
+    pub moves: usize,
+    pub won: bool,
+}
+
+/// Aggregated statistics of the simulated games
+#[derive(Serialize)]
+pub struct Stats {
+    strategy: String,
+    width: usize,
+    height: usize,
+    target: Tile,
+    games: usize,
+    wins: usize,
+    win_rate: f64,
+    moves_mean: f64,
+    score: ScoreStats,
+    /// Number of games by the highest reached tile, highest tile first
+    max_tiles: Vec<TileCount>,
+    duration: f64,
+}
+
+/// Distribution of the scores
+#[derive(Serialize)]
+struct ScoreStats {
+    mean: f64,
+    min: usize,
+    p25: usize,
+    median: usize,
+    p75: usize,
+    max: usize,
+}
+
+/// Number of games ending with the tile as the highest one
+#[derive(Serialize)]
+struct TileCount {
+    tile: Tile,
+    count: usize,
+}
+
+impl GameResult {
+    /// Creates [`GameResult`] from the finished game
+    pub fn new(board: &Board) -> Self {
+        Self {
+            score: board.score,
+            max_tile: board.max_tile(),
+            moves: board.moves(),
+            won: board.max_tile() >= board.target(),
+        }
+    }
+}
+
+impl Stats {
+    /// Aggregates the results of the games run with the given arguments
+    pub fn new(
+        args: &Args,
+        results: &[GameResult],
+        duration: Duration,
+    ) -> Self {
+        let games = results.len();
+        let wins = results.iter().filter(|r| r.won).count();
+
+        let mut max_tiles: Vec<TileCount> = vec![];
+        for result in results {
+            match max_tiles.iter_mut().find(|t| t.tile == result.max_tile) {
+                Some(count) => count.count += 1,
+                None => max_tiles.push(TileCount {
+                    tile: result.max_tile,
+                    count: 1,
+                }),
+            }
+        }
+        max_tiles.sort_by_key(|count| std::cmp::Reverse(count.tile));
+
+        let moves: usize = results.iter().map(|r| r.moves).sum();
+        Self {
+            strategy: args.strategy.to_string(),
+            width: args.width,
+            height: args.height,
+            target: args.target,
+            games,
+            wins,
+            win_rate: Self::ratio(wins, games),
+            moves_mean: Self::ratio(moves, games),
+            score: ScoreStats::new(results),
+            max_tiles,
+            duration: duration.as_secs_f64(),
+        }
+    }
+
+    /// Gets the statistics as human readable text
+    pub fn text(&self) -> String {
+        let mut res = String::new();
+        _ = writeln!(
+            res,
+            "Strategy: {}, board {}x{}, target {}",
+            self.strategy, self.width, self.height, self.target
+        );
+        _ = writeln!(res, "Games:    {} in {:.1}s", self.games, self.duration);
+        _ = writeln!(
+            res,
+            "Wins:     {} ({:.1}%)",
+            self.wins,
+            self.win_rate * 100.
+        );
+        _ = writeln!(res, "Moves:    {:.1} on average", self.moves_mean);
+        _ = writeln!(
+            res,
+            "Score:    mean {:.1}, min {}, 25% {}, median {}, 75% {}, max {}",
+            self.score.mean,
+            self.score.min,
+            self.score.p25,
+            self.score.median,
+            self.score.p75,
+            self.score.max
+        );
+        _ = writeln!(res, "\nMax tile  Games");
+        for count in self.max_tiles.iter() {
+            _ = writeln!(
+                res,
+                "{:>8}  {:>5} {:>5.1}%",
+                count.tile.to_string(),
+                count.count,
+                Self::ratio(count.count, self.games) * 100.
+            );
+        }
+        res
+    }
+
+    /// Gets the statistics as CSV with metric and value columns
+    pub fn csv(&self) -> String {
+        let mut res = String::from("metric,value\n");
+        let mut row = |metric: &str, value: String| {
+            _ = writeln!(res, "{metric},{value}");
+        };
+        row("strategy", self.strategy.clone());
+        row("width", self.width.to_string());
+        row("height", self.height.to_string());
+        row("target", self.target.value().to_string());
+        row("games", self.games.to_string());
+        row("wins", self.wins.to_string());
+        row("win_rate", self.win_rate.to_string());
+        row("moves_mean", self.moves_mean.to_string());
+        row("score_mean", self.score.mean.to_string());
+        row("score_min", self.score.min.to_string());
+        row("score_p25", self.score.p25.to_string());
+        row("score_median", self.score.median.to_string());
+        row("score_p75", self.score.p75.to_string());
+        row("score_max", self.score.max.to_string());
+        for count in self.max_tiles.iter() {
+            row(
+                &format!("max_tile_{}", count.tile.value()),
+                count.count.to_string(),
+            );
+        }
+        row("duration", self.duration.to_string());
+        res
+    }
+
+    /// Gets `part / total`, zero when there is nothing in total
+    fn ratio(part: usize, total: usize) -> f64 {
+        if total == 0 {
+            0.
+        } else {
+            part as f64 / total as f64
+        }
+    }
+}
+
+impl ScoreStats {
+    /// Gets distribution of the scores of the given games
+    fn new(results: &[GameResult]) -> Self {
+        let mut scores: Vec<_> = results.iter().map(|r| r.score).collect();
+        scores.sort_unstable();
+        let sum: usize = scores.iter().sum();
+        Self {
+            mean: Stats::ratio(sum, scores.len()),
+            min: Self::percentile(&scores, 0.),
+            p25: Self::percentile(&scores, 0.25),
+            median: Self::percentile(&scores, 0.5),
+            p75: Self::percentile(&scores, 0.75),
+            max: Self::percentile(&scores, 1.),
+        }
+    }
+
+    /// Gets score at the given percentile of the sorted scores
+    fn percentile(scores: &[usize], percentile: f64) -> usize {
+        let Some(last) = scores.len().checked_sub(1) else {
+            return 0;
+        };
+        scores[(last as f64 * percentile).round() as usize]
+    }
+}
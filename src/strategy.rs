@@ -0,0 +1,94 @@
+use std::{fmt::Display, str::FromStr};
+
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{ai::Solver, board::Board, direction::Direction};
+
+/// Strategy used for picking the moves by the [`Bot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Random possible move
+    Random,
+    /// Keeps the tiles in the bottom left corner, moves down and left when
+    /// possible, then right and up as the last option
+    Corner,
+    /// Best move found by the expectimax [`Solver`]
+    Expectimax,
+}
+
+/// Plays the game using the given [`Strategy`]
+pub struct Bot {
+    strategy: Strategy,
+    solver: Solver,
+    rng: ChaCha8Rng,
+}
+
+impl Strategy {
+    /// All the strategies
+    pub const ALL: [Strategy; 3] =
+        [Strategy::Random, Strategy::Corner, Strategy::Expectimax];
+
+    /// Gets name of the [`Strategy`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Random => "random",
+            Strategy::Corner => "corner",
+            Strategy::Expectimax => "expectimax",
+        }
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|strategy| strategy.name() == s)
+            .ok_or_else(|| format!("unknown strategy '{s}'"))
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Bot {
+    /// Creates new [`Bot`] using the given [`Strategy`], `seed` is used for
+    /// the random moves
+    pub fn new(strategy: Strategy, seed: u64) -> Self {
+        Self {
+            strategy,
+            solver: Solver::default(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Sets how many moves ahead the expectimax strategy looks
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.solver = Solver::new(depth);
+        self
+    }
+
+    /// Gets the next move on the given [`Board`], returns [`None`] when no
+    /// move is possible
+    pub fn next_move(&mut self, board: &Board) -> Option<Direction> {
+        let mut dirs = match self.strategy {
+            Strategy::Expectimax => return self.solver.best_move(board),
+            Strategy::Corner => [
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+                Direction::Up,
+            ],
+            Strategy::Random => Direction::ALL,
+        };
+        if self.strategy == Strategy::Random {
+            dirs.shuffle(&mut self.rng);
+        }
+        dirs.into_iter().find(|dir| board.clone().slide(*dir))
+    }
+}
@@ -0,0 +1,294 @@
+use std::{fmt::Display, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tui2048::tile::Tile;
+
+use crate::error::Error;
+
+/// Color used by the [`Theme`], written as `#rrggbb` or `default` for the
+/// default terminal color
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum Color {
+    #[default]
+    Default,
+    Rgb(u32),
+}
+
+/// Colors of single tile
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct TileColor {
+    pub bg: Color,
+    #[serde(default)]
+    pub fg: Color,
+}
+
+/// Colors used for rendering the game. Missing colors in the theme file
+/// are taken from the classic theme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// Name of the theme, theme files without it are named by the file
+    #[serde(default)]
+    pub name: String,
+    /// Colors of the tiles from 2 upwards, tiles higher than the last one
+    /// use the last colors
+    pub tiles: Vec<TileColor>,
+    pub empty: TileColor,
+    /// Color of the grid between the tiles
+    pub grid: Color,
+    /// Color of the controls and titles
+    pub accent: Color,
+    /// Color of the victory message and the new high score
+    pub highlight: Color,
+    /// Color of the help hint below the board
+    pub hint: Color,
+    pub error: Color,
+}
+
+impl Color {
+    /// Blends two colors, `ratio` 0 gives the first and 1 the second one.
+    /// Default color can't be blended, so the closer one is used
+    pub fn blend(&self, other: &Color, ratio: f64) -> Color {
+        let (Color::Rgb(from), Color::Rgb(to)) = (self, other) else {
+            return if ratio < 0.5 { *self } else { *other };
+        };
+        Color::Rgb((0..3).fold(0, |res, i| {
+            let shift = i * 8;
+            let from = (from >> shift & 0xff) as f64;
+            let to = (to >> shift & 0xff) as f64;
+            let channel = (from + (to - from) * ratio).round() as u32;
+            res | channel << shift
+        }))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "default" {
+            return Ok(Color::Default);
+        }
+        value
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .map(Color::Rgb)
+            .ok_or_else(|| {
+                format!("invalid color '{value}', expected '#rrggbb'")
+            })
+    }
+}
+
+impl From<Color> for String {
+    fn from(value: Color) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::Default => write!(f, "default"),
+            Color::Rgb(rgb) => write!(f, "#{rgb:06x}"),
+        }
+    }
+}
+
+impl TileColor {
+    /// Creates new [`TileColor`] with given background and foreground
+    const fn new(bg: u32, fg: Option<u32>) -> Self {
+        Self {
+            bg: Color::Rgb(bg),
+            fg: match fg {
+                Some(fg) => Color::Rgb(fg),
+                None => Color::Default,
+            },
+        }
+    }
+}
+
+impl Theme {
+    /// Gets all the built-in themes, the first one is the default
+    pub fn builtin() -> Vec<Theme> {
+        vec![
+            Self::default(),
+            Self::dark(),
+            Self::solarized(),
+            Self::high_contrast(),
+        ]
+    }
+
+    /// Loads [`Theme`] from the given TOML file
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        let mut theme: Theme = toml::from_str(&content)
+            .map_err(|e| Error::InvalidTheme(e.message().to_string()))?;
+        if theme.tiles.is_empty() {
+            return Err(Error::InvalidTheme("no tile colors".to_string()));
+        }
+        if theme.name.is_empty() {
+            theme.name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        Ok(theme)
+    }
+
+    /// Loads the built-in themes and the theme files from the themes
+    /// directory. Invalid theme files are skipped and reported in the
+    /// returned message
+    pub fn load_all() -> (Vec<Theme>, Option<String>) {
+        let mut themes = Self::builtin();
+        let Some(dir) =
+            dirs::config_dir().map(|dir| dir.join("tui2048").join("themes"))
+        else {
+            return (themes, None);
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return (themes, None);
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+        let mut error = None;
+        for path in paths {
+            match Self::load(&path) {
+                Ok(theme) => themes.push(theme),
+                Err(_) => {
+                    let name = path.file_name().unwrap_or_default();
+                    error = Some(format!("Invalid {}", name.to_string_lossy()));
+                }
+            }
+        }
+        (themes, error)
+    }
+
+    /// Gets colors of the given tile
+    pub fn tile(&self, tile: &Tile) -> TileColor {
+        if tile.is_empty() {
+            return self.empty;
+        }
+        let index = (tile.exp() as usize - 1).min(self.tiles.len() - 1);
+        self.tiles[index]
+    }
+
+    /// Dark theme, which is easy on the eyes in dark terminals
+    fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            tiles: vec![
+                TileColor::new(0x3c3a32, Some(0xeee4da)),
+                TileColor::new(0x4a463a, Some(0xeee4da)),
+                TileColor::new(0x8c5a2b, Some(0xf9f6f2)),
+                TileColor::new(0x9c4f2a, Some(0xf9f6f2)),
+                TileColor::new(0xa8432c, Some(0xf9f6f2)),
+                TileColor::new(0xb3361d, Some(0xf9f6f2)),
+                TileColor::new(0x8f7a2c, Some(0xf9f6f2)),
+                TileColor::new(0x9a8328, Some(0xf9f6f2)),
+                TileColor::new(0xa58c24, Some(0xf9f6f2)),
+                TileColor::new(0xb09520, Some(0xf9f6f2)),
+                TileColor::new(0xbb9e1c, Some(0xf9f6f2)),
+                TileColor::new(0x5c3c6e, Some(0xf9f6f2)),
+            ],
+            empty: TileColor::new(0x2e2c28, None),
+            grid: Color::Rgb(0x1c1b18),
+            accent: Color::Rgb(0x56b6c2),
+            highlight: Color::Rgb(0xe5c07b),
+            hint: Color::Rgb(0x7f7f7f),
+            error: Color::Rgb(0xe06c75),
+        }
+    }
+
+    /// Theme using the solarized palette
+    fn solarized() -> Self {
+        Self {
+            name: "solarized".to_string(),
+            tiles: vec![
+                TileColor::new(0xeee8d5, Some(0x586e75)),
+                TileColor::new(0x93a1a1, Some(0x002b36)),
+                TileColor::new(0xb58900, Some(0xfdf6e3)),
+                TileColor::new(0xcb4b16, Some(0xfdf6e3)),
+                TileColor::new(0xdc322f, Some(0xfdf6e3)),
+                TileColor::new(0xd33682, Some(0xfdf6e3)),
+                TileColor::new(0x6c71c4, Some(0xfdf6e3)),
+                TileColor::new(0x268bd2, Some(0xfdf6e3)),
+                TileColor::new(0x2aa198, Some(0xfdf6e3)),
+                TileColor::new(0x859900, Some(0xfdf6e3)),
+                TileColor::new(0x073642, Some(0xb58900)),
+                TileColor::new(0x002b36, Some(0xfdf6e3)),
+            ],
+            empty: TileColor::new(0x586e75, None),
+            grid: Color::Rgb(0x073642),
+            accent: Color::Rgb(0x2aa198),
+            highlight: Color::Rgb(0xb58900),
+            hint: Color::Rgb(0x839496),
+            error: Color::Rgb(0xdc322f),
+        }
+    }
+
+    /// Theme with highly contrasting colors
+    fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            tiles: vec![
+                TileColor::new(0xffffff, Some(0x000000)),
+                TileColor::new(0xffff00, Some(0x000000)),
+                TileColor::new(0x00ffff, Some(0x000000)),
+                TileColor::new(0x00ff00, Some(0x000000)),
+                TileColor::new(0xff00ff, Some(0x000000)),
+                TileColor::new(0xff0000, Some(0xffffff)),
+                TileColor::new(0x0000ff, Some(0xffffff)),
+                TileColor::new(0x008000, Some(0xffffff)),
+                TileColor::new(0x800080, Some(0xffffff)),
+                TileColor::new(0x808000, Some(0xffffff)),
+                TileColor::new(0x000080, Some(0xffffff)),
+                TileColor::new(0x000000, Some(0xffffff)),
+            ],
+            empty: TileColor::new(0x404040, None),
+            grid: Color::Rgb(0x000000),
+            accent: Color::Rgb(0xffff00),
+            highlight: Color::Rgb(0x00ff00),
+            hint: Color::Rgb(0xffffff),
+            error: Color::Rgb(0xff0000),
+        }
+    }
+}
+
+impl Default for Theme {
+    /// Classic theme with the colors of the original game
+    fn default() -> Self {
+        Self {
+            name: "classic".to_string(),
+            tiles: vec![
+                TileColor::new(0xeee4da, None),
+                TileColor::new(0xede0c8, None),
+                TileColor::new(0xf2b179, None),
+                TileColor::new(0xf59563, None),
+                TileColor::new(0xf67c5f, None),
+                TileColor::new(0xf65e3b, None),
+                TileColor::new(0xedcf72, None),
+                TileColor::new(0xedcc61, None),
+                TileColor::new(0xedc850, None),
+                TileColor::new(0xedc53f, None),
+                TileColor::new(0xedc22e, None),
+                TileColor::new(0x969696, None),
+            ],
+            empty: TileColor::new(0x969696, None),
+            grid: Color::Rgb(0x797979),
+            accent: Color::Rgb(0x00ffff),
+            highlight: Color::Rgb(0xffff00),
+            hint: Color::Rgb(0x808080),
+            error: Color::Rgb(0xff0000),
+        }
+    }
+}
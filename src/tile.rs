@@ -1,101 +1,91 @@
-use std::ops::AddAssign;
+use std::fmt::Display;
 
-use termint::{
-    enums::{bg::Bg, fg::Fg},
-    geometry::constrain::Constrain,
-    widgets::layout::Layout,
-};
+use serde::{Deserialize, Serialize};
 
-use crate::raw_span::RawSpan;
-
-/// Represents tile in the 2048 board
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Tile(u16);
+/// Represents tile in the 2048 board. It's stored as exponent of two, so
+/// `Tile(0)` is empty tile, `Tile(1)` is 2, `Tile(2)` is 4 and so on
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct Tile(u8);
 
 impl Tile {
-    /// Creates new [`Tile`]
-    pub fn new(value: u16) -> Self {
-        Self(value)
+    /// Empty [`Tile`]
+    pub const EMPTY: Tile = Tile(0);
+    /// Highest [`Tile`], its value still fits to `u64`
+    pub const MAX: Tile = Tile(63);
+
+    /// Creates new [`Tile`] with value equal to `2^exp`
+    pub const fn from_exp(exp: u8) -> Self {
+        if exp > Self::MAX.0 {
+            Self::MAX
+        } else {
+            Self(exp)
+        }
     }
 
-    /// Gets value of the [`Tile`]
-    pub fn value(&self) -> u16 {
+    /// Gets exponent of the [`Tile`] value
+    pub fn exp(&self) -> u8 {
         self.0
     }
 
-    /// Gets tile as termint widget
-    pub fn get(&self) -> Layout {
-        let db = self.color();
-
-        let mut tile = Layout::vertical();
-        tile.add_child(
-            RawSpan::new(" ▆▆▆▆▆").fg(Fg::Hex(db)).bg(Bg::Hex(0x797979)),
-            Constrain::Length(1),
-        );
-
-        tile.add_child(
-            RawSpan::new(format!(" {}{}", Bg::Hex(db), self.value_str()))
-                .bg(Bg::Hex(0x797979)),
-            Constrain::Length(1),
-        );
+    /// Gets value of the [`Tile`]
+    pub fn value(&self) -> u64 {
+        match self.0 {
+            0 => 0,
+            exp => 1 << exp,
+        }
+    }
 
-        tile.add_child(
-            RawSpan::new(format!(" {}▂▂▂▂▂", Bg::Hex(db)))
-                .bg(Bg::Hex(0x797979))
-                .fg(Fg::Hex(0x797979)),
-            Constrain::Length(1),
-        );
-        tile
+    /// Checks whether the [`Tile`] is empty
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
     }
 
-    /// Gets [`Tile`] color based on its value
-    fn color(&self) -> u32 {
-        match self.value() {
-            2 => 0xeee4da,
-            4 => 0xede0c8,
-            8 => 0xf2b179,
-            16 => 0xf59563,
-            32 => 0xf67c5f,
-            64 => 0xf65e3b,
-            128 => 0xedcf72,
-            256 => 0xedcc61,
-            512 => 0xedc850,
-            1024 => 0xedc53f,
-            2048 => 0xedc22e,
-            _ => 0x969696,
-        }
+    /// Checks whether the [`Tile`] can be the target needed for victory,
+    /// which has to be greater than 2
+    pub fn is_valid_target(&self) -> bool {
+        self.0 > 1
     }
 
-    /// Gets [`Tile`] value as string and centered so its length is 5
-    fn value_str(&self) -> String {
-        if self.value() == 0 {
-            return "     ".to_string();
+    /// Merges [`Tile`] with the other one. Returns [`None`] when the tiles
+    /// are different or when the merged tile would be greater than
+    /// [`Tile::MAX`]
+    pub fn merge(&self, other: &Tile) -> Option<Tile> {
+        if self != other || self.is_empty() || *self >= Self::MAX {
+            return None;
         }
-        let pad = 5 - self.value().to_string().len();
-        let pad_r = pad / 2;
-        format!(
-            "{}{}{}",
-            " ".repeat(pad - pad_r),
-            self.value(),
-            " ".repeat(pad_r)
-        )
+        Some(Tile(self.0 + 1))
     }
 }
 
-impl AddAssign for Tile {
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0
+impl Display for Tile {
+    /// Writes the value, abbreviated when it has more than 5 digits
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 < 17 {
+            return write!(f, "{}", self.value());
+        }
+        let unit = ["K", "M", "G", "T", "P", "E"][self.0 as usize / 10 - 1];
+        write!(f, "{}{unit}", 1 << (self.0 % 10))
     }
 }
 
-impl From<u16> for Tile {
-    fn from(value: u16) -> Self {
-        Tile(value)
+impl TryFrom<u64> for Tile {
+    type Error = String;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::EMPTY),
+            1 => Err("tile value can't be 1".to_string()),
+            v if v.is_power_of_two() => Ok(Self(v.trailing_zeros() as u8)),
+            v => Err(format!("tile value {v} is not power of two")),
+        }
     }
 }
 
-impl Into<u16> for Tile {
-    fn into(self) -> u16 {
-        self.0
+impl From<Tile> for u64 {
+    fn from(value: Tile) -> Self {
+        value.value()
     }
 }